@@ -0,0 +1,409 @@
+use std::io;
+use std::io::{BufWriter, Cursor, Read, Write};
+use std::io::Result as Res;
+
+/**
+	Finalizes a writer adapter and returns the writer it wraps.
+
+	Adapters may hold on to data or state until they're finished, so call this instead of dropping them. Since adapters are writers themselves, they can be stacked, and finishing them one after the other recovers the base writer.
+
+	## Examples
+
+	```
+	use std::io::BufWriter;
+	use endio::{ChecksumWriter, Finish, LEWrite};
+
+	let mut writer = ChecksumWriter::new(BufWriter::new(vec![]));
+	writer.ewrite(42u8).unwrap();
+	assert_eq!(writer.checksum(), 0x09b9265b);
+
+	let writer = writer.finish().unwrap().finish().unwrap();
+	assert_eq!(writer, b"\x2a");
+	```
+*/
+pub trait Finish: Sized {
+	/// The writer this adapter wraps.
+	type Inner;
+
+	/// Flushes any pending data and returns the wrapped writer.
+	fn finish(self) -> Res<Self::Inner>;
+}
+
+impl<W: Write> Finish for BufWriter<W> {
+	type Inner = W;
+
+	fn finish(self) -> Res<W> {
+		self.into_inner().map_err(|e| e.into_error())
+	}
+}
+
+/// Counts the number of bytes written through it.
+pub struct CountingWriter<W> {
+	inner: W,
+	count: u64,
+}
+
+impl<W> CountingWriter<W> {
+	pub fn new(inner: W) -> Self {
+		Self { inner, count: 0 }
+	}
+
+	/// The number of bytes written so far.
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+	fn write(&mut self, buf: &[u8]) -> Res<usize> {
+		let written = self.inner.write(buf)?;
+		self.count += written as u64;
+		Ok(written)
+	}
+
+	fn flush(&mut self) -> Res<()> {
+		self.inner.flush()
+	}
+}
+
+impl<W: Write> Finish for CountingWriter<W> {
+	type Inner = W;
+
+	fn finish(mut self) -> Res<W> {
+		self.inner.flush()?;
+		Ok(self.inner)
+	}
+}
+
+const fn crc32_table() -> [u32; 256] {
+	let mut table = [0; 256];
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as u32;
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+			bit += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+}
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Updates a running CRC-32 (IEEE) state with the bytes. Start with `!0` and invert the result to get the checksum.
+pub(crate) fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+	for &byte in bytes {
+		crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+	}
+	crc
+}
+
+/// Computes the CRC-32 (IEEE) checksum of all bytes written through it.
+pub struct ChecksumWriter<W> {
+	inner: W,
+	crc: u32,
+}
+
+impl<W> ChecksumWriter<W> {
+	pub fn new(inner: W) -> Self {
+		Self { inner, crc: !0 }
+	}
+
+	/// The CRC-32 of the bytes written so far.
+	pub fn checksum(&self) -> u32 {
+		!self.crc
+	}
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+	fn write(&mut self, buf: &[u8]) -> Res<usize> {
+		let written = self.inner.write(buf)?;
+		self.crc = crc32_update(self.crc, &buf[..written]);
+		Ok(written)
+	}
+
+	fn flush(&mut self) -> Res<()> {
+		self.inner.flush()
+	}
+}
+
+impl<W: Write> Finish for ChecksumWriter<W> {
+	type Inner = W;
+
+	fn finish(mut self) -> Res<W> {
+		self.inner.flush()?;
+		Ok(self.inner)
+	}
+}
+
+/// Reverses the bit order of every byte read or written through it, for LSB-first bit streams.
+pub struct BitReversing<T> {
+	inner: T,
+}
+
+impl<T> BitReversing<T> {
+	pub fn new(inner: T) -> Self {
+		Self { inner }
+	}
+
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+}
+
+impl<R: Read> Read for BitReversing<R> {
+	fn read(&mut self, buf: &mut [u8]) -> Res<usize> {
+		let read = self.inner.read(buf)?;
+		for byte in &mut buf[..read] {
+			*byte = byte.reverse_bits();
+		}
+		Ok(read)
+	}
+}
+
+impl<W: Write> Write for BitReversing<W> {
+	fn write(&mut self, buf: &[u8]) -> Res<usize> {
+		let mut reversed = [0; 256];
+		let len = buf.len().min(reversed.len());
+		for (rev, byte) in reversed.iter_mut().zip(&buf[..len]) {
+			*rev = byte.reverse_bits();
+		}
+		self.inner.write(&reversed[..len])
+	}
+
+	fn flush(&mut self) -> Res<()> {
+		self.inner.flush()
+	}
+}
+
+impl<W: Write> Finish for BitReversing<W> {
+	type Inner = W;
+
+	fn finish(mut self) -> Res<W> {
+		self.inner.flush()?;
+		Ok(self.inner)
+	}
+}
+
+/**
+	A reader that knows its current offset in the stream.
+
+	## Examples
+
+	```
+	use endio::{LERead, Positioned, PositionTrackingReader};
+
+	let mut reader = PositionTrackingReader::new(&b"\x2a\x2a"[..]);
+	let _: u8 = reader.eread().unwrap();
+	let err = reader.with_offset(|r| r.eread::<bool>()).unwrap_err();
+	assert_eq!(err.to_string(), "bool had value 42, expected 0 or 1 at offset 1");
+	```
+*/
+pub trait Positioned {
+	/// The number of bytes from the start of the stream.
+	fn position(&self) -> u64;
+
+	/// Runs the read, and if it fails, adds the offset the read started at to the error message. The error kind is kept.
+	fn with_offset<T, F: FnOnce(&mut Self) -> Res<T>>(&mut self, read: F) -> Res<T> {
+		let start = self.position();
+		read(self).map_err(|e| io::Error::new(e.kind(), format!("{} at offset {}", e, start)))
+	}
+}
+
+impl<T> Positioned for Cursor<T> {
+	fn position(&self) -> u64 {
+		Cursor::position(self)
+	}
+}
+
+/// Counts the number of bytes read through it, for readers that don't track their position themselves.
+pub struct PositionTrackingReader<R> {
+	inner: R,
+	position: u64,
+}
+
+impl<R> PositionTrackingReader<R> {
+	pub fn new(inner: R) -> Self {
+		Self { inner, position: 0 }
+	}
+
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+}
+
+impl<R: Read> Read for PositionTrackingReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> Res<usize> {
+		let read = self.inner.read(buf)?;
+		self.position += read as u64;
+		Ok(read)
+	}
+}
+
+impl<R> Positioned for PositionTrackingReader<R> {
+	fn position(&self) -> u64 {
+		self.position
+	}
+}
+
+/**
+	A fixed-size ring buffer over a byte slice, written to with `Write` and drained with `Read`.
+
+	Writes wrap around the end of the buffer. A write that doesn't fit into the free space returns a `WriteZero` error without writing anything, so unread data is never overwritten. Note that a `Serialize` may consist of several writes, in which case the ones before the failing write have already been made.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, RingWriter};
+
+	let mut buf = [0; 4];
+	let mut ring = RingWriter::new(&mut buf);
+	ring.ewrite(0xbaadu16).unwrap();
+	assert_eq!(ring.eread::<u16>().unwrap(), 0xbaad);
+	ring.ewrite(0xdeadbeefu32).unwrap();
+	assert!(ring.ewrite(42u8).is_err());
+	assert_eq!(ring.eread::<u32>().unwrap(), 0xdeadbeef);
+	```
+*/
+pub struct RingWriter<'a> {
+	buf: &'a mut [u8],
+	start: usize,
+	len: usize,
+}
+
+impl<'a> RingWriter<'a> {
+	pub fn new(buf: &'a mut [u8]) -> Self {
+		Self { buf, start: 0, len: 0 }
+	}
+
+	/// The number of unread bytes.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// The number of bytes that can be written before unread data would be overwritten.
+	pub fn free(&self) -> usize {
+		self.buf.len() - self.len
+	}
+}
+
+impl Write for RingWriter<'_> {
+	fn write(&mut self, data: &[u8]) -> Res<usize> {
+		if data.len() > self.free() {
+			return Err(io::Error::new(io::ErrorKind::WriteZero, "write would overwrite unread data"));
+		}
+		let end = (self.start + self.len) % self.buf.len().max(1);
+		let first = data.len().min(self.buf.len() - end);
+		self.buf[end..end + first].copy_from_slice(&data[..first]);
+		self.buf[..data.len() - first].copy_from_slice(&data[first..]);
+		self.len += data.len();
+		Ok(data.len())
+	}
+
+	fn flush(&mut self) -> Res<()> {
+		Ok(())
+	}
+}
+
+impl Read for RingWriter<'_> {
+	fn read(&mut self, out: &mut [u8]) -> Res<usize> {
+		let count = out.len().min(self.len);
+		let first = count.min(self.buf.len() - self.start);
+		out[..first].copy_from_slice(&self.buf[self.start..self.start + first]);
+		out[first..count].copy_from_slice(&self.buf[..count - first]);
+		self.start = (self.start + count) % self.buf.len().max(1);
+		self.len -= count;
+		Ok(count)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+	use std::io::{BufWriter, Cursor};
+	use super::{ChecksumWriter, CountingWriter, Finish, Positioned, PositionTrackingReader, RingWriter};
+
+	#[test]
+	fn checksum_over_counting() {
+		use crate::BEWrite;
+		let mut writer = ChecksumWriter::new(CountingWriter::new(vec![]));
+		writer.ewrite(&b"123456789"[..]).unwrap();
+		assert_eq!(writer.checksum(), 0xcbf43926);
+		let writer = writer.finish().unwrap();
+		assert_eq!(writer.count(), 9);
+		let writer = writer.finish().unwrap();
+		assert_eq!(writer, b"123456789");
+	}
+
+	#[test]
+	fn finish_buf_writer() {
+		use crate::LEWrite;
+		let mut writer = BufWriter::new(vec![]);
+		writer.ewrite(0xbaadu16).unwrap();
+		assert_eq!(writer.finish().unwrap(), b"\xad\xba");
+	}
+
+	#[test]
+	fn position_tracking() {
+		use crate::LERead;
+		let mut reader = PositionTrackingReader::new(&b"\xad\xba\x01\x2a\x00"[..]);
+		let _: u16 = reader.eread().unwrap();
+		assert_eq!(reader.position(), 2);
+		let _: bool = reader.with_offset(|r| r.eread()).unwrap();
+		let err = reader.with_offset(|r| r.eread::<bool>()).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert!(err.to_string().ends_with("at offset 3"));
+		assert_eq!(reader.position(), 4);
+	}
+
+	#[test]
+	fn position_cursor() {
+		use crate::BERead;
+		let mut reader = Cursor::new(&b"\xba\xad\xf0"[..]);
+		let _: u16 = reader.eread().unwrap();
+		assert_eq!(Positioned::position(&reader), 2);
+		let err = reader.with_offset(|r| r.eread::<u16>()).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		assert!(err.to_string().ends_with("at offset 2"));
+	}
+
+	#[test]
+	fn ring_wraparound() {
+		use crate::{BERead, BEWrite};
+		let mut buf = [0; 7];
+		let mut ring = RingWriter::new(&mut buf);
+		for i in 0..10u32 {
+			ring.ewrite(0xbaad0000 | i).unwrap();
+			assert_eq!(ring.len(), 4);
+			assert_eq!(ring.eread::<u32>().unwrap(), 0xbaad0000 | i);
+			assert!(ring.is_empty());
+		}
+		ring.ewrite(0xf00du16).unwrap();
+		ring.ewrite(0xbeefu16).unwrap();
+		ring.ewrite(42u8).unwrap();
+		assert_eq!(ring.free(), 2);
+		assert_eq!(ring.eread::<(u16, u16, u8)>().unwrap(), (0xf00d, 0xbeef, 42));
+	}
+
+	#[test]
+	fn ring_full() {
+		use std::io::Write;
+		use crate::LEWrite;
+		let mut buf = [0; 4];
+		let mut ring = RingWriter::new(&mut buf);
+		ring.ewrite(0xbaadu16).unwrap();
+		let err = ring.ewrite(0xdeadbeefu32).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+		assert_eq!(ring.len(), 2);
+		ring.write_all(b"\x01\x02").unwrap();
+		assert_eq!(ring.free(), 0);
+		assert!(ring.write_all(b"\x03").is_err());
+	}
+}
@@ -0,0 +1,102 @@
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{CountingWriter, Deserialize, Endianness, Positioned, PositionTrackingReader, Serialize};
+
+/// The number of padding bytes after `size` bytes to reach a multiple of `align`.
+fn padding(size: u64, align: u64) -> u64 {
+	(align - size % align) % align
+}
+
+/**
+	A record padded with zeros to a multiple of `ALIGN` bytes, like a C struct with trailing padding.
+
+	The padding is computed from the number of bytes the value actually wrote or read, so it also works for values without a fixed size. When reading, the padding is skipped without checking its contents. `ALIGN` must not be zero, and writing or reading an `Aligned<0, T>` fails to compile.
+
+	## Examples
+
+	```
+	use endio::{Aligned, LERead, LEWrite};
+
+	let mut writer = vec![];
+	writer.ewrite(Aligned::<4, _>((0xbaadu16, 42u8))).unwrap();
+	assert_eq!(writer, b"\xad\xba\x2a\x00");
+
+	let mut reader = &writer[..];
+	let Aligned(val) = reader.eread::<Aligned<4, (u16, u8)>>().unwrap();
+	assert_eq!(val, (0xbaad, 42));
+	assert!(reader.is_empty());
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Aligned<const ALIGN: usize, T>(pub T);
+
+impl<const ALIGN: usize, T> Aligned<ALIGN, T> {
+	const ALIGNMENT: u64 = {
+		assert!(ALIGN != 0, "Aligned needs a nonzero alignment");
+		ALIGN as u64
+	};
+}
+
+impl<E: Endianness, W: Write, const ALIGN: usize, T> Serialize<E, W> for Aligned<ALIGN, T> where T: for<'a> Serialize<E, CountingWriter<&'a mut W>> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let mut counter = CountingWriter::new(&mut *writer);
+		E::serialize(self.0, &mut counter)?;
+		let pad = padding(counter.count(), Self::ALIGNMENT);
+		io::copy(&mut io::repeat(0).take(pad), writer)?;
+		Ok(())
+	}
+}
+
+impl<E: Endianness, R: Read, const ALIGN: usize, T> Deserialize<E, R> for Aligned<ALIGN, T> where T: for<'a> Deserialize<E, PositionTrackingReader<&'a mut R>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut tracker = PositionTrackingReader::new(&mut *reader);
+		let value = E::deserialize(&mut tracker)?;
+		let pad = padding(tracker.position(), Self::ALIGNMENT);
+		if io::copy(&mut reader.take(pad), &mut io::sink())? != pad {
+			return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "record ended before its padding"));
+		}
+		Ok(Aligned(value))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Aligned;
+
+	#[test]
+	fn five_bytes_to_8() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(Aligned::<8, _>((0xbaadf00du32, true))).unwrap();
+		writer.ewrite(42u8).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d\x01\x00\x00\x00\x2a");
+		let mut reader = &writer[..];
+		let val: Aligned<8, (u32, bool)> = reader.eread().unwrap();
+		assert_eq!(val.0, (0xbaadf00d, true));
+		let next: u8 = reader.eread().unwrap();
+		assert_eq!(next, 42);
+	}
+
+	#[test]
+	fn already_aligned() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(Aligned::<4, _>(0xbaadf00du32)).unwrap();
+		assert_eq!(writer, b"\x0d\xf0\xad\xba");
+		let mut reader = &writer[..];
+		let val: Aligned<4, u32> = reader.eread().unwrap();
+		assert_eq!(val.0, 0xbaadf00d);
+	}
+
+	#[test]
+	fn missing_padding() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x2a\x00"[..];
+		let err = reader.eread::<Aligned<4, u8>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		assert_eq!(err.to_string(), "record ended before its padding");
+	}
+}
@@ -0,0 +1,68 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{BitReversing, Deserialize, Endianness, Serialize};
+
+/**
+	Reads/writes the wrapped value with the bit order of every byte reversed, for protocols that transmit bytes LSB-first.
+
+	The value is serialized as usual, and then each of its bytes is reversed, so the byte order still follows the endianness. Reading reverses the bytes back before deserializing them. Use `BitReversing` to reverse a whole stream instead.
+
+	## Examples
+
+	```
+	use endio::{BitReversed, LERead, LEWrite};
+
+	let mut writer = vec![];
+	writer.ewrite(BitReversed(0x0001u16)).unwrap();
+	assert_eq!(writer, b"\x80\x00");
+
+	let mut reader = &writer[..];
+	let BitReversed(val) = reader.eread::<BitReversed<u16>>().unwrap();
+	assert_eq!(val, 1);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitReversed<T>(pub T);
+
+impl<E: Endianness, W: Write, T> Serialize<E, W> for BitReversed<T> where T: for<'a> Serialize<E, BitReversing<&'a mut W>> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0, &mut BitReversing::new(writer))
+	}
+}
+
+impl<E: Endianness, R: Read, T> Deserialize<E, R> for BitReversed<T> where T: for<'a> Deserialize<E, BitReversing<&'a mut R>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(BitReversed(E::deserialize(&mut BitReversing::new(reader))?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BitReversed;
+
+	#[test]
+	fn u8() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(BitReversed(0x01u8)).unwrap();
+		writer.ewrite(BitReversed(0xf0u8)).unwrap();
+		assert_eq!(writer, b"\x80\x0f");
+		let mut reader = &writer[..];
+		let val: BitReversed<u8> = reader.eread().unwrap();
+		assert_eq!(val.0, 0x01);
+		let val: BitReversed<u8> = reader.eread().unwrap();
+		assert_eq!(val.0, 0xf0);
+	}
+
+	#[test]
+	fn multi_byte() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(BitReversed((0xbaadu16, "io"))).unwrap();
+		assert_eq!(writer, b"\x5d\xb5\x00\x00\x00\x40\x96\xf6");
+		let mut reader = &writer[..];
+		let val: BitReversed<(u16, String)> = reader.eread().unwrap();
+		assert_eq!(val.0, (0xbaad, String::from("io")));
+	}
+}
@@ -0,0 +1,92 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{read_bytes, read_len};
+use crate::serialize::write_len;
+
+/**
+	A `Vec<bool>` packed into bits, with a `u32` prefix of the number of bits.
+
+	The bits are packed into `ceil(len / 8)` bytes, the first bool in the least significant bit of the first byte. The padding bits of the last byte are written as zeros and ignored when reading.
+
+	## Examples
+
+	```
+	use endio::{BitVec, LERead, LEWrite};
+
+	let bits = BitVec(vec![true, false, true, true, false, false, false, false, true]);
+	let mut writer = vec![];
+	writer.ewrite(&bits).unwrap();
+	assert_eq!(writer, b"\x09\x00\x00\x00\x0d\x01");
+
+	let mut reader = &writer[..];
+	let read: BitVec = reader.eread().unwrap();
+	assert_eq!(read, bits);
+	```
+*/
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec(pub Vec<bool>);
+
+impl<E: Endianness, W: Write> Serialize<E, W> for &BitVec {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.0.len())?;
+		let bytes: Vec<u8> = self.0.chunks(8).map(|chunk| {
+			chunk.iter().enumerate().fold(0, |byte, (i, &bit)| byte | (bit as u8) << i)
+		}).collect();
+		writer.write_all(&bytes)
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for BitVec {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let bytes = read_bytes(reader, len.div_ceil(8))?;
+		Ok(BitVec((0..len).map(|i| bytes[i / 8] & 1 << (i % 8) != 0).collect()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BitVec;
+
+	#[test]
+	fn thirteen() {
+		use crate::{BERead, BEWrite};
+		let bits = BitVec((0..13).map(|i| i % 3 == 0).collect());
+		let mut writer = vec![];
+		writer.ewrite(&bits).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x0d\x49\x12");
+		let mut reader = &writer[..];
+		let val: BitVec = reader.eread().unwrap();
+		assert_eq!(val, bits);
+	}
+
+	#[test]
+	fn empty() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(&BitVec(vec![])).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+		let mut reader = &writer[..];
+		let val: BitVec = reader.eread().unwrap();
+		assert!(val.0.is_empty());
+	}
+
+	#[test]
+	fn padding_ignored() {
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\xfe"[..];
+		let val: BitVec = reader.eread().unwrap();
+		assert_eq!(val.0, [false, true]);
+	}
+
+	#[test]
+	fn truncated() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x09\x00\x00\x00\xff"[..];
+		let err = reader.eread::<BitVec>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
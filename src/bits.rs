@@ -0,0 +1,326 @@
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Finish, Serialize};
+
+/**
+	A writer that can write values of any number of bits, not just whole bytes.
+
+	Bits are packed most significant bit first, in the order they're written, regardless of endianness. Since `BitWriter` is a writer itself, regular types can be written to it as well; if the stream isn't byte-aligned at that point, their bytes are split across byte boundaries.
+
+	Call `finish` when done, which pads the last byte with zero bits.
+
+	## Examples
+
+	```
+	use endio::{Bits, BitWriter, BEWrite, Finish};
+
+	let mut writer = BitWriter::new(vec![]);
+	writer.ewrite(Bits::<3>(0b101)).unwrap();
+	writer.ewrite(0xffu8).unwrap();
+	assert_eq!(writer.finish().unwrap(), b"\xbf\xe0");
+	```
+*/
+pub struct BitWriter<W> {
+	inner: W,
+	acc: u8,
+	len: u32,
+}
+
+impl<W: Write> BitWriter<W> {
+	pub fn new(inner: W) -> Self {
+		Self { inner, acc: 0, len: 0 }
+	}
+
+	/**
+		Writes the low `count` bits of the value.
+
+		Returns an `InvalidInput` error if the value doesn't fit in `count` bits.
+
+		# Panics
+
+		Panics if `count` is more than 64.
+	*/
+	pub fn write_bits(&mut self, value: u64, count: u32) -> Res<()> {
+		assert!(count <= 64, "can't write more than 64 bits at once");
+		if count < 64 && value >> count != 0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} doesn't fit in {} bits", value, count)));
+		}
+		for i in (0..count).rev() {
+			self.acc = self.acc << 1 | (value >> i & 1) as u8;
+			self.len += 1;
+			if self.len == 8 {
+				self.inner.write_all(&[self.acc])?;
+				self.acc = 0;
+				self.len = 0;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<W: Write> Write for BitWriter<W> {
+	fn write(&mut self, buf: &[u8]) -> Res<usize> {
+		if self.len == 0 {
+			return self.inner.write(buf);
+		}
+		for &byte in buf {
+			self.write_bits(byte as u64, 8)?;
+		}
+		Ok(buf.len())
+	}
+
+	/// Flushes the wrapped writer. Bits of an unfinished byte are kept until `finish`.
+	fn flush(&mut self) -> Res<()> {
+		self.inner.flush()
+	}
+}
+
+impl<W: Write> Finish for BitWriter<W> {
+	type Inner = W;
+
+	fn finish(mut self) -> Res<W> {
+		if self.len != 0 {
+			self.inner.write_all(&[self.acc << (8 - self.len)])?;
+		}
+		self.inner.flush()?;
+		Ok(self.inner)
+	}
+}
+
+/**
+	A reader for the bit-packed format written by `BitWriter`.
+
+	Regular types can be read from it as well, with their bytes split across byte boundaries if the stream isn't byte-aligned.
+
+	## Examples
+
+	```
+	use endio::{Bits, BitReader, BERead};
+
+	let mut reader = BitReader::new(&b"\xbf\xe0"[..]);
+	let Bits(bits) = reader.eread::<Bits<3>>().unwrap();
+	let byte: u8 = reader.eread().unwrap();
+	assert_eq!((bits, byte), (0b101, 0xff));
+	```
+*/
+pub struct BitReader<R> {
+	inner: R,
+	acc: u8,
+	len: u32,
+}
+
+impl<R: Read> BitReader<R> {
+	pub fn new(inner: R) -> Self {
+		Self { inner, acc: 0, len: 0 }
+	}
+
+	/**
+		Reads `count` bits into the low bits of a `u64`.
+
+		# Panics
+
+		Panics if `count` is more than 64.
+	*/
+	pub fn read_bits(&mut self, count: u32) -> Res<u64> {
+		assert!(count <= 64, "can't read more than 64 bits at once");
+		let mut value = 0;
+		for _ in 0..count {
+			if self.len == 0 {
+				let mut byte = [0];
+				self.inner.read_exact(&mut byte)?;
+				self.acc = byte[0];
+				self.len = 8;
+			}
+			self.len -= 1;
+			value = value << 1 | (self.acc >> self.len & 1) as u64;
+		}
+		Ok(value)
+	}
+
+	/// Skips the rest of the current byte, so that the next read starts at a byte boundary.
+	pub fn align(&mut self) {
+		self.len = 0;
+	}
+
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+}
+
+/// Once the wrapped reader is at its end, the bits left of a partially read byte are treated as padding, and reading returns `Ok(0)`.
+impl<R: Read> Read for BitReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> Res<usize> {
+		if self.len == 0 || buf.is_empty() {
+			return self.inner.read(buf);
+		}
+		let mut byte = [0];
+		if self.inner.read(&mut byte)? == 0 {
+			return Ok(0);
+		}
+		buf[0] = self.acc << (8 - self.len) | byte[0] >> self.len;
+		self.acc = byte[0];
+		Ok(1)
+	}
+}
+
+/**
+	An unsigned integer of `N` bits, for use with `BitWriter` and `BitReader`.
+
+	This lets bit fields be written with the usual `Serialize`/`Deserialize` traits, by implementing them for `BitWriter<W>`/`BitReader<R>` instead of any writer/reader. Writing returns an `InvalidInput` error if the value doesn't fit in `N` bits. `N` can be at most 64, and writing or reading a wider `Bits` fails to compile.
+
+	## Examples
+
+	```
+	use std::io::{Read, Result, Write};
+	use endio::{Bits, BitReader, BitWriter, BERead, BEWrite, Deserialize, Endianness, Finish, Serialize};
+
+	#[derive(Debug, PartialEq)]
+	struct Header {
+		id: u16,
+		flags: u8,
+	}
+
+	impl<E: Endianness, W: Write> Serialize<E, BitWriter<W>> for &Header {
+		fn serialize(self, writer: &mut BitWriter<W>) -> Result<()> {
+			E::serialize(Bits::<12>(self.id as u64), writer)?;
+			E::serialize(Bits::<4>(self.flags as u64), writer)
+		}
+	}
+
+	impl<E: Endianness, R: Read> Deserialize<E, BitReader<R>> for Header {
+		fn deserialize(reader: &mut BitReader<R>) -> Result<Self> {
+			let Bits(id) = E::deserialize::<_, Bits<12>>(reader)?;
+			let Bits(flags) = E::deserialize::<_, Bits<4>>(reader)?;
+			Ok(Header { id: id as u16, flags: flags as u8 })
+		}
+	}
+
+	let header = Header { id: 0xabc, flags: 0xd };
+	let mut writer = BitWriter::new(vec![]);
+	writer.ewrite(&header).unwrap();
+	let bytes = writer.finish().unwrap();
+	assert_eq!(bytes, b"\xab\xcd");
+
+	let mut reader = BitReader::new(&bytes[..]);
+	let read: Header = reader.eread().unwrap();
+	assert_eq!(read, header);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bits<const N: u32>(pub u64);
+
+impl<const N: u32> Bits<N> {
+	const COUNT: u32 = {
+		assert!(N <= 64, "Bits can hold at most 64 bits");
+		N
+	};
+}
+
+impl<E: Endianness, W: Write, const N: u32> Serialize<E, BitWriter<W>> for Bits<N> {
+	fn serialize(self, writer: &mut BitWriter<W>) -> Res<()> {
+		writer.write_bits(self.0, Self::COUNT)
+	}
+}
+
+impl<E: Endianness, R: Read, const N: u32> Deserialize<E, BitReader<R>> for Bits<N> {
+	fn deserialize(reader: &mut BitReader<R>) -> Res<Self> {
+		reader.read_bits(Self::COUNT).map(Bits)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::{Read, Write};
+	use std::io::Result as Res;
+
+	use crate::{Deserialize, Endianness, Finish, Serialize};
+	use super::{Bits, BitReader, BitWriter};
+
+	#[derive(Debug, PartialEq)]
+	struct Packed {
+		wide: u16,
+		narrow: u8,
+	}
+
+	impl<E: Endianness, W: Write> Serialize<E, BitWriter<W>> for &Packed {
+		fn serialize(self, writer: &mut BitWriter<W>) -> Res<()> {
+			E::serialize(Bits::<12>(self.wide as u64), writer)?;
+			E::serialize(Bits::<4>(self.narrow as u64), writer)
+		}
+	}
+
+	impl<E: Endianness, R: Read> Deserialize<E, BitReader<R>> for Packed {
+		fn deserialize(reader: &mut BitReader<R>) -> Res<Self> {
+			let Bits(wide) = E::deserialize::<_, Bits<12>>(reader)?;
+			let Bits(narrow) = E::deserialize::<_, Bits<4>>(reader)?;
+			Ok(Packed { wide: wide as u16, narrow: narrow as u8 })
+		}
+	}
+
+	#[test]
+	fn twelve_and_four() {
+		use crate::{BERead, BEWrite};
+		let packed = Packed { wide: 0xbaa, narrow: 0xd };
+		let mut writer = BitWriter::new(vec![]);
+		writer.ewrite(&packed).unwrap();
+		let bytes = writer.finish().unwrap();
+		assert_eq!(bytes, b"\xba\xad");
+		let mut reader = BitReader::new(&bytes[..]);
+		let val: Packed = reader.eread().unwrap();
+		assert_eq!(val, packed);
+		assert!(reader.into_inner().is_empty());
+	}
+
+	#[test]
+	fn unaligned_bytes() {
+		use crate::{LERead, LEWrite};
+		let mut writer = BitWriter::new(vec![]);
+		writer.ewrite(Bits::<4>(0xb)).unwrap();
+		writer.ewrite(0xf00du16).unwrap();
+		let bytes = writer.finish().unwrap();
+		assert_eq!(bytes, b"\xb0\xdf\x00");
+		let mut reader = BitReader::new(&bytes[..]);
+		let _: Bits<4> = reader.eread().unwrap();
+		let val: u16 = reader.eread().unwrap();
+		assert_eq!(val, 0xf00d);
+	}
+
+	#[test]
+	fn align() {
+		let mut reader = BitReader::new(&b"\xff\x2a"[..]);
+		assert_eq!(reader.read_bits(3).unwrap(), 0b111);
+		reader.align();
+		assert_eq!(reader.read_bits(8).unwrap(), 0x2a);
+	}
+
+	#[test]
+	fn too_wide() {
+		use std::io;
+		use crate::BEWrite;
+		let mut writer = BitWriter::new(vec![]);
+		let err = writer.ewrite(Bits::<4>(16)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "16 doesn't fit in 4 bits");
+	}
+
+	#[test]
+	fn truncated() {
+		use std::io;
+		let mut reader = BitReader::new(&b"\xff"[..]);
+		let err = reader.read_bits(12).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn eof_after_partial_byte() {
+		use std::io::Read;
+		let mut reader = BitReader::new(&b"\xba\xad"[..]);
+		assert_eq!(reader.read_bits(4).unwrap(), 0xb);
+		let mut rest = vec![];
+		assert_eq!(reader.read_to_end(&mut rest).unwrap(), 1);
+		assert_eq!(rest, b"\xaa");
+		assert_eq!(reader.read(&mut [0]).unwrap(), 0);
+	}
+}
@@ -0,0 +1,88 @@
+use std::io;
+use std::io::Result as Res;
+
+use crate::Endianness;
+use crate::deserialize::{invalid_data, read_len};
+
+/**
+	Implement this for types that borrow their data from the reader's buffer instead of copying it.
+
+	This is the zero-copy counterpart of `Deserialize`, for parsing buffers that are already in memory. The lifetime `'a` is the lifetime of the buffer, which the returned value can borrow from. Read these types with `read_borrowed`.
+
+	`&'a [u8]` and `&'a str` are implemented for `&'a [u8]` readers, in the same formats as `Box<[u8]>` and `String`.
+
+	## Examples
+
+	```
+	use endio::LERead;
+
+	let data = b"\x02\x00\x00\x00\xba\xad\x05\x00\x00\x00endio";
+	let mut reader = &data[..];
+	let bytes: &[u8] = reader.read_borrowed().unwrap();
+	let string: &str = reader.read_borrowed().unwrap();
+	assert_eq!(bytes, b"\xba\xad");
+	assert_eq!(string, "endio");
+	assert_eq!(string.as_ptr(), data[10..].as_ptr());
+	```
+*/
+pub trait DeserializeBorrowed<'a, E: Endianness, R>: Sized {
+	/// Deserializes the type by reading from the reader, borrowing from its buffer.
+	fn deserialize_borrowed(reader: &mut R) -> Res<Self>;
+}
+
+/// Reads a `u32` length prefix, followed by that many bytes.
+impl<'a, E: Endianness> DeserializeBorrowed<'a, E, &'a [u8]> for &'a [u8] {
+	fn deserialize_borrowed(reader: &mut &'a [u8]) -> Res<Self> {
+		let len = read_len::<E, _>(reader)?;
+		if reader.len() < len {
+			return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"));
+		}
+		let (bytes, rest) = reader.split_at(len);
+		*reader = rest;
+		Ok(bytes)
+	}
+}
+
+/// Reads a `u32` length prefix, followed by that many bytes of UTF-8, returning an `InvalidData` error if they aren't valid UTF-8.
+impl<'a, E: Endianness> DeserializeBorrowed<'a, E, &'a [u8]> for &'a str {
+	fn deserialize_borrowed(reader: &mut &'a [u8]) -> Res<Self> {
+		let mut rest = *reader;
+		let bytes: &[u8] = DeserializeBorrowed::<E, _>::deserialize_borrowed(&mut rest)?;
+		let string = std::str::from_utf8(bytes).map_err(|e| invalid_data(format!("string is not valid UTF-8: {}", e)))?;
+		*reader = rest;
+		Ok(string)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	#[test]
+	fn backing_store() {
+		use crate::{BERead, BEWrite};
+		let mut store = vec![];
+		store.ewrite("endio").unwrap();
+		store.ewrite(&Box::<[u8]>::from(&b"\xba\xad"[..])).unwrap();
+		let mut reader = &store[..];
+		let string: &str = reader.read_borrowed().unwrap();
+		let bytes: &[u8] = reader.read_borrowed().unwrap();
+		assert_eq!(string, "endio");
+		assert_eq!(string.as_ptr(), store[4..].as_ptr());
+		assert_eq!(bytes, b"\xba\xad");
+		assert_eq!(bytes.as_ptr(), store[13..].as_ptr());
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn invalid() {
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\xc3\x28"[..];
+		let err = reader.read_borrowed::<&str>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(reader.len(), 6);
+		let mut reader = &b"\x05\x00\x00\x00end"[..];
+		let err = reader.read_borrowed::<&[u8]>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
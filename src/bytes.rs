@@ -0,0 +1,67 @@
+//! Conversions between primitives and their raw bytes, for when you don't need a reader or writer.
+//!
+//! ## Examples
+//!
+//! ```
+//! use endio::bytes::{from_bytes_le, to_bytes_be};
+//!
+//! assert_eq!(to_bytes_be(0xbaadu16), [0xba, 0xad]);
+//! assert_eq!(from_bytes_le::<u16, 2>([0xad, 0xba]), 0xbaad);
+//! ```
+
+/// A primitive that converts to and from exactly `N` bytes. As with `Serialize`, `usize` and `isize` aren't implemented, since their size depends on the platform.
+pub trait ByteRepr<const N: usize>: Sized {
+	fn to_be_repr(self) -> [u8; N];
+	fn to_le_repr(self) -> [u8; N];
+	fn from_be_repr(bytes: [u8; N]) -> Self;
+	fn from_le_repr(bytes: [u8; N]) -> Self;
+}
+
+/// Returns the value's bytes in big endian.
+pub fn to_bytes_be<T: ByteRepr<N>, const N: usize>(value: T) -> [u8; N] { value.to_be_repr() }
+/// Returns the value's bytes in little endian.
+pub fn to_bytes_le<T: ByteRepr<N>, const N: usize>(value: T) -> [u8; N] { value.to_le_repr() }
+/// Creates a value from its bytes in big endian.
+pub fn from_bytes_be<T: ByteRepr<N>, const N: usize>(bytes: [u8; N]) -> T { T::from_be_repr(bytes) }
+/// Creates a value from its bytes in little endian.
+pub fn from_bytes_le<T: ByteRepr<N>, const N: usize>(bytes: [u8; N]) -> T { T::from_le_repr(bytes) }
+
+macro_rules! impl_byte_repr {
+	($($t:ident)*) => {
+		$(
+			impl ByteRepr<{std::mem::size_of::<$t>()}> for $t {
+				fn to_be_repr(self) -> [u8; std::mem::size_of::<$t>()] { self.to_be_bytes() }
+				fn to_le_repr(self) -> [u8; std::mem::size_of::<$t>()] { self.to_le_bytes() }
+				fn from_be_repr(bytes: [u8; std::mem::size_of::<$t>()]) -> Self { Self::from_be_bytes(bytes) }
+				fn from_le_repr(bytes: [u8; std::mem::size_of::<$t>()]) -> Self { Self::from_le_bytes(bytes) }
+			}
+		)*
+	}
+}
+
+impl_byte_repr!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64);
+
+#[cfg(test)]
+mod tests {
+	use super::{from_bytes_be, from_bytes_le, to_bytes_be, to_bytes_le};
+
+	#[test]
+	fn floats() {
+		assert_eq!(to_bytes_be(642.6135f32), 642.6135f32.to_be_bytes());
+		assert_eq!(to_bytes_le(1337.0083f64), 1337.0083f64.to_le_bytes());
+		assert_eq!(from_bytes_be::<f32, _>(642.6135f32.to_be_bytes()).to_bits(), 642.6135f32.to_bits());
+		assert_eq!(from_bytes_le::<f64, _>(1337.0083f64.to_le_bytes()).to_bits(), 1337.0083f64.to_bits());
+	}
+
+	#[test]
+	fn integers() {
+		assert_eq!(to_bytes_be(0xbaadu16), [0xba, 0xad]);
+		assert_eq!(to_bytes_le(0xbaadu16), [0xad, 0xba]);
+		assert_eq!(to_bytes_be(-2i32), (-2i32).to_be_bytes());
+		assert_eq!(to_bytes_le(0xdeadbeefbaadf00du64), 0xdeadbeefbaadf00du64.to_le_bytes());
+		assert_eq!(to_bytes_be(u128::MAX - 1), (u128::MAX - 1).to_be_bytes());
+		assert_eq!(from_bytes_be::<u32, _>([0xde, 0xad, 0xbe, 0xef]), 0xdeadbeef);
+		assert_eq!(from_bytes_le::<i16, _>([0xfe, 0xff]), -2);
+		assert_eq!(from_bytes_le::<u8, _>([0x2a]), 42);
+	}
+}
@@ -0,0 +1,69 @@
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+macro_rules! impl_canonical {
+	($name:ident, $t:ident, $bits:ident) => {
+		#[doc = concat!("An `", stringify!($t), "` that writes every NaN as the same bit pattern, that of `", stringify!($t), "::NAN`.")]
+		///
+		/// This way, records that are logically equal are also equal byte for byte, for example for hashing. All other values, including the sign of zero, are written as-is. Reading doesn't canonicalize, the bits are read verbatim.
+		#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+		pub struct $name(pub $t);
+
+		impl<E: Endianness, W> Serialize<E, W> for $name where $bits: Serialize<E, W> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				let value = if self.0.is_nan() { $t::NAN } else { self.0 };
+				E::serialize(value.to_bits(), writer)
+			}
+		}
+
+		impl<E: Endianness, R> Deserialize<E, R> for $name where $bits: Deserialize<E, R> {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let bits: $bits = E::deserialize(reader)?;
+				Ok(Self($t::from_bits(bits)))
+			}
+		}
+	}
+}
+
+impl_canonical!(CanonicalF32, f32, u32);
+impl_canonical!(CanonicalF64, f64, u64);
+
+#[cfg(test)]
+mod tests {
+	use super::{CanonicalF32, CanonicalF64};
+
+	#[test]
+	fn nans_are_canonical() {
+		use crate::BEWrite;
+		let nans = [f64::NAN, -f64::NAN, f64::from_bits(0x7ff0_0000_0000_0001), f64::from_bits(0xfff8_dead_beef_0000)];
+		for nan in nans {
+			assert!(nan.is_nan());
+			let mut writer = vec![];
+			writer.ewrite(CanonicalF64(nan)).unwrap();
+			assert_eq!(writer, f64::NAN.to_bits().to_be_bytes());
+		}
+		let mut writer = vec![];
+		writer.ewrite(CanonicalF32(f32::from_bits(0xffc0_0001))).unwrap();
+		assert_eq!(writer, f32::NAN.to_bits().to_be_bytes());
+	}
+
+	#[test]
+	fn other_values_unchanged() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.ewrite(CanonicalF64(-0.0)).unwrap();
+		writer.ewrite(CanonicalF64(1337.0083)).unwrap();
+		assert_eq!(writer[..8], (-0.0f64).to_le_bytes());
+		assert_eq!(writer[8..], 1337.0083f64.to_le_bytes());
+	}
+
+	#[test]
+	fn read_bits_verbatim() {
+		use crate::LERead;
+		let bits = 0xfff8_dead_beef_0000u64;
+		let mut reader = &bits.to_le_bytes()[..];
+		let val: CanonicalF64 = reader.eread().unwrap();
+		assert_eq!(val.0.to_bits(), bits);
+	}
+}
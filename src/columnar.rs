@@ -0,0 +1,101 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::read_len;
+use crate::serialize::write_len;
+
+/**
+	A sequence of records in struct-of-arrays layout: A `u32` length prefix, followed by the first field of every record, then the second field of every record, and so on.
+
+	Records are tuples, with one column per tuple element. To write your own structs in this layout, convert them to tuples of their fields, or of references to them. Writing is implemented for `&[(A, B, ...)]` and reading for `Vec<(A, B, ...)>`, for tuples of 2 to 12 elements.
+
+	## Examples
+
+	```
+	use endio::{Columnar, LERead, LEWrite};
+
+	let points = [(1u8, 10u16), (2, 20), (3, 30)];
+	let mut writer = vec![];
+	writer.ewrite(Columnar(&points[..])).unwrap();
+	assert_eq!(writer, b"\x03\x00\x00\x00\x01\x02\x03\x0a\x00\x14\x00\x1e\x00");
+
+	let mut reader = &writer[..];
+	let Columnar(read) = reader.eread::<Columnar<Vec<(u8, u16)>>>().unwrap();
+	assert_eq!(read, points);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Columnar<T>(pub T);
+
+macro_rules! impl_columnar {
+	($($name:ident $idx:tt)+) => {
+		impl<E: Endianness, W: Write, $($name: Copy+Serialize<E, W>),+> Serialize<E, W> for Columnar<&[($($name,)+)]> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				write_len::<E, W>(writer, self.0.len())?;
+				$(
+					for record in self.0 {
+						E::serialize(record.$idx, writer)?;
+					}
+				)+
+				Ok(())
+			}
+		}
+
+		impl<E: Endianness, R: Read, $($name: Deserialize<E, R>),+> Deserialize<E, R> for Columnar<Vec<($($name,)+)>> {
+			#[allow(non_snake_case)]
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let len = read_len::<E, R>(reader)?;
+				$(let mut $name = (0..len).map(|_| E::deserialize::<R, $name>(reader)).collect::<Res<Vec<_>>>()?.into_iter();)+
+				Ok(Columnar((0..len).filter_map(|_| Some(($($name.next()?,)+))).collect()))
+			}
+		}
+	}
+}
+
+impl_columnar!(A 0 B 1);
+impl_columnar!(A 0 B 1 C 2);
+impl_columnar!(A 0 B 1 C 2 D 3);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10);
+impl_columnar!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10 M 11);
+
+#[cfg(test)]
+mod tests {
+	use super::Columnar;
+
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	struct Sample {
+		id: u16,
+		level: u8,
+	}
+
+	#[test]
+	fn struct_of_two_fields() {
+		use crate::{BERead, BEWrite};
+		let samples = [Sample { id: 0xbaad, level: 1 }, Sample { id: 0xf00d, level: 2 }, Sample { id: 0x2a2b, level: 3 }];
+		let records: Vec<_> = samples.iter().map(|s| (s.id, s.level)).collect();
+		let mut writer = vec![];
+		writer.ewrite(Columnar(&records[..])).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x03\xba\xad\xf0\x0d\x2a\x2b\x01\x02\x03");
+		let mut reader = &writer[..];
+		let Columnar(read) = reader.eread::<Columnar<Vec<(u16, u8)>>>().unwrap();
+		let read: Vec<_> = read.into_iter().map(|(id, level)| Sample { id, level }).collect();
+		assert_eq!(read, samples);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn truncated() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\x01\x00\x02\x00\x2a"[..];
+		let err = reader.eread::<Columnar<Vec<(u16, u8)>>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
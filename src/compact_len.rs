@@ -0,0 +1,205 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, PolicyString, Serialize, Strict, StringPolicy};
+use crate::config::check_len;
+use crate::deserialize::{invalid_data, read_bytes};
+
+/// The byte that marks a compact length as being a following `u32`.
+const ESCAPE: u8 = 255;
+
+/**
+	A length written in one byte if it's at most 254, and as a byte of 255 followed by a `u32` otherwise.
+
+	Reading returns an `InvalidData` error if an escaped length is 254 or less, since it should have been written in one byte. Writing returns an `InvalidInput` error if the length doesn't fit in a `u32`.
+
+	## Examples
+
+	```
+	use endio::{CompactLen, LERead, LEWrite};
+
+	let mut writer = vec![];
+	writer.ewrite(CompactLen(254)).unwrap();
+	writer.ewrite(CompactLen(255)).unwrap();
+	assert_eq!(writer, b"\xfe\xff\xff\x00\x00\x00");
+
+	let mut reader = &writer[..];
+	assert_eq!(reader.eread::<CompactLen>().unwrap(), CompactLen(254));
+	assert_eq!(reader.eread::<CompactLen>().unwrap(), CompactLen(255));
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactLen(pub usize);
+
+impl<E: Endianness, W: Write> Serialize<E, W> for CompactLen {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		if self.0 < ESCAPE as usize {
+			return E::serialize(self.0 as u8, writer);
+		}
+		let len = u32::try_from(self.0).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length does not fit in u32 prefix"))?;
+		E::serialize(ESCAPE, writer)?;
+		E::serialize(len, writer)
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for CompactLen {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let first: u8 = E::deserialize(reader)?;
+		if first != ESCAPE {
+			return Ok(CompactLen(first as usize));
+		}
+		let len: u32 = E::deserialize(reader)?;
+		if len < ESCAPE as u32 {
+			return Err(invalid_data(format!("escaped compact length {} should have been written in one byte", len)));
+		}
+		Ok(CompactLen(len as usize))
+	}
+}
+
+/**
+	A slice or string with a `CompactLen` prefix instead of the usual `u32`.
+
+	Slices `&[T]` and strings `&str` can be written, and `Vec<T>`, `String` and `PolicyString<P>` can be read. The prefix is the number of elements for slices and the number of bytes for strings, and is checked against the current `ReadConfig`.
+
+	## Examples
+
+	```
+	use endio::{BERead, BEWrite, CompactLenPrefixed};
+
+	let mut writer = vec![];
+	writer.ewrite(CompactLenPrefixed("endio")).unwrap();
+	assert_eq!(writer, b"\x05endio");
+
+	let mut reader = &writer[..];
+	let CompactLenPrefixed(string): CompactLenPrefixed<String> = reader.eread().unwrap();
+	assert_eq!(string, "endio");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactLenPrefixed<T>(pub T);
+
+fn read_compact_len<E: Endianness, R: Read>(reader: &mut R) -> Res<usize> {
+	let CompactLen(len) = E::deserialize(reader)?;
+	check_len(len)
+}
+
+impl<E: Endianness, W: Write, T> Serialize<E, W> for CompactLenPrefixed<&[T]> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(CompactLen(self.0.len()), writer)?;
+		for elem in self.0 {
+			E::serialize(elem, writer)?;
+		}
+		Ok(())
+	}
+}
+
+impl<E: Endianness, W: Write> Serialize<E, W> for CompactLenPrefixed<&str> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(CompactLen(self.0.len()), writer)?;
+		writer.write_all(self.0.as_bytes())
+	}
+}
+
+/// The elements are collected without preallocating, so a corrupt prefix results in an `UnexpectedEof` error instead of a huge allocation.
+impl<E: Endianness, R: Read, T: Deserialize<E, R>> Deserialize<E, R> for CompactLenPrefixed<Vec<T>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_compact_len::<E, R>(reader)?;
+		let vec = (0..len).map(|_| E::deserialize(reader)).collect::<Res<_>>()?;
+		Ok(CompactLenPrefixed(vec))
+	}
+}
+
+/// Returns an `InvalidData` error if the bytes aren't valid UTF-8.
+impl<E: Endianness, R: Read> Deserialize<E, R> for CompactLenPrefixed<String> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let CompactLenPrefixed(string) = E::deserialize::<R, CompactLenPrefixed<PolicyString<Strict>>>(reader)?;
+		Ok(CompactLenPrefixed(string.0))
+	}
+}
+
+/// Decodes the bytes with the policy `P`.
+impl<E: Endianness, R: Read, P: StringPolicy> Deserialize<E, R> for CompactLenPrefixed<PolicyString<P>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_compact_len::<E, R>(reader)?;
+		Ok(CompactLenPrefixed(PolicyString::new(P::decode(read_bytes(reader, len)?)?)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::{CompactLen, CompactLenPrefixed};
+
+	fn round_trip(count: usize, prefix: &[u8]) {
+		use crate::{LERead, LEWrite};
+		let bytes: Vec<u8> = (0..count).map(|i| i as u8).collect();
+		let mut writer = vec![];
+		writer.ewrite(CompactLenPrefixed(&bytes[..])).unwrap();
+		assert_eq!(&writer[..prefix.len()], prefix);
+		assert_eq!(writer.len(), prefix.len() + count);
+		let mut reader = &writer[..];
+		let CompactLenPrefixed(val): CompactLenPrefixed<Vec<u8>> = reader.eread().unwrap();
+		assert_eq!(val, bytes);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn zero() {
+		round_trip(0, b"\x00");
+	}
+
+	#[test]
+	fn largest_inline() {
+		round_trip(254, b"\xfe");
+	}
+
+	#[test]
+	fn smallest_escaped() {
+		round_trip(255, b"\xff\xff\x00\x00\x00");
+	}
+
+	#[test]
+	fn large() {
+		round_trip(70000, b"\xff\x70\x11\x01\x00");
+	}
+
+	#[test]
+	fn escape_is_big_endian_with_be() {
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.ewrite(CompactLen(0x1234)).unwrap();
+		assert_eq!(writer, b"\xff\x00\x00\x12\x34");
+	}
+
+	#[test]
+	fn non_canonical() {
+		use crate::LERead;
+		let mut reader = &b"\xff\xfe\x00\x00\x00"[..];
+		let err = reader.eread::<CompactLen>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "escaped compact length 254 should have been written in one byte");
+	}
+
+	#[test]
+	fn string() {
+		use crate::{BERead, BEWrite};
+		let string = "e".repeat(300);
+		let mut writer = vec![];
+		writer.ewrite(CompactLenPrefixed(&string[..])).unwrap();
+		assert_eq!(&writer[..5], b"\xff\x00\x00\x01\x2c");
+		let mut reader = &writer[..];
+		let CompactLenPrefixed(val): CompactLenPrefixed<String> = reader.eread().unwrap();
+		assert_eq!(val, string);
+	}
+
+	#[test]
+	fn string_lossy() {
+		use crate::{LERead, Lossy, PolicyString};
+		let mut reader = &b"\x02e\xff"[..];
+		let CompactLenPrefixed(val): CompactLenPrefixed<PolicyString<Lossy>> = reader.eread().unwrap();
+		assert_eq!(val.0, "e\u{fffd}");
+	}
+}
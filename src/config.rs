@@ -0,0 +1,109 @@
+use std::cell::Cell;
+use std::io::Result as Res;
+
+use crate::deserialize::invalid_data;
+
+thread_local! {
+	static MAX_LEN: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/**
+	Settings for all reads done within `scope`, so that a single setting covers every type read by a parser.
+
+	`scope` is the only way to apply the settings. They apply to all reads on the current thread for the duration of the closure, including those done by nested `Deserialize` impls, and are restored afterwards. The settings are thread-local state, so they don't apply to reads done on another thread, such as a thread spawned within `scope`, or an async task that continues on another worker thread. Reads there fall back to trusting the length prefixes, so call `scope` on the thread that does the reading.
+
+	## Examples
+
+	```
+	use endio::{LERead, ReadConfig};
+
+	let config = ReadConfig::new().max_len(4);
+	let mut reader = &b"\x05\x00\x00\x00endio"[..];
+	let err = config.scope(|| reader.eread::<String>()).unwrap_err();
+	assert_eq!(err.to_string(), "length 5 exceeds the maximum of 4");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadConfig {
+	max_len: Option<usize>,
+}
+
+impl ReadConfig {
+	/// Creates a config without any limits.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Limits the length prefix of every length-prefixed type, such as strings, slices and maps, returning an `InvalidData` error for any length above `max_len`.
+	pub fn max_len(mut self, max_len: usize) -> Self {
+		self.max_len = Some(max_len);
+		self
+	}
+
+	/// Runs the closure with this config applied on the current thread. See the type's documentation for what this covers.
+	pub fn scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+		struct Restore(Option<usize>);
+
+		impl Drop for Restore {
+			fn drop(&mut self) {
+				MAX_LEN.with(|max_len| max_len.set(self.0));
+			}
+		}
+
+		let _restore = Restore(MAX_LEN.with(|max_len| max_len.replace(self.max_len)));
+		f()
+	}
+}
+
+/// Checks a length prefix against the maximum length of the current config.
+pub(crate) fn check_len(len: usize) -> Res<usize> {
+	match MAX_LEN.with(Cell::get) {
+		Some(max_len) if len > max_len => Err(invalid_data(format!("length {} exceeds the maximum of {}", len, max_len))),
+		_ => Ok(len),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::ReadConfig;
+
+	#[test]
+	fn max_len() {
+		use crate::BERead;
+		let config = ReadConfig::new().max_len(2);
+		let mut reader = &b"\x00\x00\x00\x02\xba\xad\x00\x00\x00\x03\xba\xad\xf0"[..];
+		config.scope(|| {
+			let val: Box<[u8]> = reader.eread().unwrap();
+			assert_eq!(*val, [0xba, 0xad]);
+			let err = reader.eread::<Box<[u8]>>().unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		});
+	}
+
+	#[test]
+	fn nested() {
+		use std::collections::HashMap;
+		use crate::LERead;
+		let config = ReadConfig::new().max_len(4);
+		let mut reader = &b"\x01\x00\x00\x00\x2a\x05\x00\x00\x00endio"[..];
+		let err = config.scope(|| reader.eread::<HashMap<u8, String>>()).unwrap_err();
+		assert_eq!(err.to_string(), "failed to read value of map entry 0: length 5 exceeds the maximum of 4");
+	}
+
+	#[test]
+	fn restored() {
+		use crate::LERead;
+		ReadConfig::new().max_len(0).scope(|| {
+			ReadConfig::new().scope(|| {
+				let mut reader = &b"\x01\x00\x00\x00a"[..];
+				assert_eq!(reader.eread::<String>().unwrap(), "a");
+			});
+			let mut reader = &b"\x01\x00\x00\x00a"[..];
+			assert!(reader.eread::<String>().is_err());
+		});
+		let mut reader = &b"\x01\x00\x00\x00a"[..];
+		assert_eq!(reader.eread::<String>().unwrap(), "a");
+	}
+}
@@ -0,0 +1,98 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/**
+	A `Vec<T>` with its elements separated by the delimiter byte `D` and no length prefix.
+
+	Writing puts `D` between the elements, but not after the last one. Reading consumes the reader until EOF, splits the bytes at every `D`, and reads one element from each part. An empty reader reads as an empty vec.
+
+	This only works if the serialized form of `T` never contains `D`, since there is no escaping: a `T` containing the delimiter byte would be split in two when it's read back. Use this for types like ASCII text or numbers known to avoid the delimiter, otherwise prefer a length-prefixed `Vec<T>`. Reading returns an `InvalidData` error if an element doesn't use up all bytes of its part.
+
+	## Examples
+
+	```
+	use endio::{BERead, BEWrite, DelimitedVec};
+
+	let records: DelimitedVec<u16, b'\n'> = DelimitedVec(vec![0x6869, 0x796f]);
+	let mut writer = vec![];
+	writer.ewrite(&records).unwrap();
+	assert_eq!(writer, b"hi\nyo");
+
+	let mut reader = &writer[..];
+	let read: DelimitedVec<u16, b'\n'> = reader.eread().unwrap();
+	assert_eq!(read, records);
+	```
+*/
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DelimitedVec<T, const D: u8>(pub Vec<T>);
+
+impl<E: Endianness, W: Write, T, const D: u8> Serialize<E, W> for &DelimitedVec<T, D> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		for (i, elem) in self.0.iter().enumerate() {
+			if i != 0 {
+				writer.write_all(&[D])?;
+			}
+			E::serialize(elem, writer)?;
+		}
+		Ok(())
+	}
+}
+
+impl<E: Endianness, R: Read, T, const D: u8> Deserialize<E, R> for DelimitedVec<T, D> where for<'a> T: Deserialize<E, &'a [u8]> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut bytes = vec![];
+		reader.read_to_end(&mut bytes)?;
+		if bytes.is_empty() {
+			return Ok(DelimitedVec(vec![]));
+		}
+		let mut vec = vec![];
+		for mut part in bytes.split(|&b| b == D) {
+			vec.push(E::deserialize(&mut part)?);
+			if !part.is_empty() {
+				return Err(invalid_data(format!("record had {} trailing bytes", part.len())));
+			}
+		}
+		Ok(DelimitedVec(vec))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::DelimitedVec;
+
+	#[test]
+	fn three_records() {
+		use crate::{LERead, LEWrite};
+		let records: DelimitedVec<[u8; 3], b'\n'> = DelimitedVec(vec![*b"abc", *b"def", *b"ghi"]);
+		let mut writer = vec![];
+		writer.ewrite(&records).unwrap();
+		assert_eq!(writer, b"abc\ndef\nghi");
+		let mut reader = &writer[..];
+		let val: DelimitedVec<[u8; 3], b'\n'> = reader.eread().unwrap();
+		assert_eq!(val, records);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn empty() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(&DelimitedVec::<u16, 0>(vec![])).unwrap();
+		assert!(writer.is_empty());
+		let mut reader = &writer[..];
+		let val: DelimitedVec<u16, 0> = reader.eread().unwrap();
+		assert!(val.0.is_empty());
+	}
+
+	#[test]
+	fn trailing_bytes() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"ab\ncde"[..];
+		let err = reader.eread::<DelimitedVec<u16, b'\n'>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+}
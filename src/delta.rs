@@ -0,0 +1,117 @@
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{invalid_data, read_len};
+use crate::serialize::write_len;
+use crate::varint::{read_varint, write_varint};
+
+/**
+	A sorted sequence of `u64`s, stored as the gaps between them for compactness.
+
+	Written as a `u32` prefix of the number of values, followed by the first value and then the difference of each value to the previous one, each as an unsigned LEB128 varint: 7 bits per byte, least significant group first, with the high bit set on all but the last byte. Small gaps take up a single byte regardless of how large the values are.
+
+	Any `T: AsRef<[u64]>` can be written, and `Vec<u64>` can be read. Writing returns an `InvalidInput` error if the values aren't sorted in ascending order; equal values are allowed. Reading returns an `InvalidData` error if a varint or the sum of the gaps overflows a `u64`.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, SortedDeltaVarints};
+
+	let mut writer = vec![];
+	writer.ewrite(SortedDeltaVarints([1000, 1001, 1005])).unwrap();
+	assert_eq!(writer, b"\x03\x00\x00\x00\xe8\x07\x01\x04");
+
+	let mut reader = &writer[..];
+	let SortedDeltaVarints(values): SortedDeltaVarints<Vec<u64>> = reader.eread().unwrap();
+	assert_eq!(values, [1000, 1001, 1005]);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SortedDeltaVarints<T>(pub T);
+
+impl<E: Endianness, W: Write, T: AsRef<[u64]>> Serialize<E, W> for SortedDeltaVarints<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let values = self.0.as_ref();
+		if let Some(i) = values.windows(2).position(|pair| pair[0] > pair[1]) {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("value {} at index {} is smaller than the one before it", values[i + 1], i + 1)));
+		}
+		write_len::<E, W>(writer, values.len())?;
+		let mut encoded = vec![];
+		let mut prev = 0;
+		for &value in values {
+			write_varint(&mut encoded, value - prev)?;
+			prev = value;
+		}
+		writer.write_all(&encoded)
+	}
+}
+
+/// The values are collected without preallocating, so a corrupt prefix results in an `UnexpectedEof` error instead of a huge allocation.
+impl<E: Endianness, R: Read> Deserialize<E, R> for SortedDeltaVarints<Vec<u64>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let mut values = vec![];
+		let mut prev = 0u64;
+		for _ in 0..len {
+			let gap = read_varint(reader)?;
+			prev = prev.checked_add(gap).ok_or_else(|| invalid_data("sum of gaps overflows u64".into()))?;
+			values.push(prev);
+		}
+		Ok(SortedDeltaVarints(values))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::SortedDeltaVarints;
+
+	#[test]
+	fn round_trip() {
+		use crate::{BERead, BEWrite};
+		let values = [0, 0, 127, 128, 1 << 40, u64::MAX];
+		let mut writer = vec![];
+		writer.ewrite(SortedDeltaVarints(&values)).unwrap();
+		assert_eq!(&writer[..9], b"\x00\x00\x00\x06\x00\x00\x7f\x01\x80");
+		let mut reader = &writer[..];
+		let SortedDeltaVarints(val): SortedDeltaVarints<Vec<u64>> = reader.eread().unwrap();
+		assert_eq!(val, values);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn empty() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(SortedDeltaVarints(Vec::new())).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+		let mut reader = &writer[..];
+		let SortedDeltaVarints(val): SortedDeltaVarints<Vec<u64>> = reader.eread().unwrap();
+		assert!(val.is_empty());
+	}
+
+	#[test]
+	fn not_sorted() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		let err = writer.ewrite(SortedDeltaVarints([1, 5, 3])).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "value 3 at index 2 is smaller than the one before it");
+		assert!(writer.is_empty());
+	}
+
+	#[test]
+	fn overflow() {
+		use crate::LERead;
+		let mut reader = &b"\x01\x00\x00\x00\xff\xff\xff\xff\xff\xff\xff\xff\xff\x7f"[..];
+		let err = reader.eread::<SortedDeltaVarints<Vec<u64>>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "varint overflows u64");
+		let mut reader = &b"\x02\x00\x00\x00\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01\x01"[..];
+		let err = reader.eread::<SortedDeltaVarints<Vec<u64>>>().unwrap_err();
+		assert_eq!(err.to_string(), "sum of gaps overflows u64");
+	}
+}
@@ -1,9 +1,23 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::ffi::OsString;
+use std::hash::{BuildHasher, Hash};
 use std::io;
 use std::io::Read;
 use std::io::Result as Res;
 use std::mem::size_of;
+use std::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroU8};
+use std::ops::{ControlFlow, Range, RangeFrom, RangeFull, RangeTo};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::AtomicBool;
 
-use crate::{ERead, Endianness};
+use crate::{DeserializeBorrowed, ERead, Endianness, Strict};
+use crate::config::check_len;
+use crate::string_policy::read_string;
 
 /**
 	Implement this for your types to be able to `read` them.
@@ -58,6 +72,52 @@ use crate::{ERead, Endianness};
 	# }
 	```
 
+	### Round trip a struct:
+
+	Implementing `Deserialize` symmetrically to the struct's `Serialize` (see there), reading the fields in the same order they were written, lets the value be read back exactly.
+
+	```
+	#[derive(Debug, PartialEq)]
+	struct Example {
+		a: u8,
+		b: bool,
+		c: u32,
+	}
+	{
+		use std::io::{Result, Write};
+		use endio::{Deserialize, Endianness, ERead, EWrite, Serialize};
+
+		impl<E: Endianness, W: Write + EWrite<E>> Serialize<E, W> for &Example {
+			fn serialize(self, writer: &mut W) -> Result<()> {
+				writer.ewrite(self.a)?;
+				writer.ewrite(self.b)?;
+				writer.ewrite(self.c)
+			}
+		}
+
+		impl<E: Endianness, R: ERead<E>> Deserialize<E, R> for Example {
+			fn deserialize(reader: &mut R) -> Result<Self> {
+				let a = reader.eread()?;
+				let b = reader.eread()?;
+				let c = reader.eread()?;
+				Ok(Example { a, b, c })
+			}
+		}
+	}
+	{
+		use endio::{BERead, BEWrite};
+
+		let e = Example { a: 42, b: true, c: 754187983 };
+		let mut writer = vec![];
+		writer.ewrite(&e).unwrap();
+
+		let mut reader = &writer[..];
+		let read: Example = reader.eread().unwrap();
+		assert_eq!(read, e);
+		assert!(reader.is_empty());
+	}
+	```
+
 	### Deserialize a primitive / something where you need the bare `std::io::Read` functionality:
 
 	Note how the trait bound for `R` is `Read`.
@@ -122,6 +182,251 @@ pub trait Deserialize<E: Endianness, R>: Sized {
 	}
 }
 
+/// Creates the `InvalidData` error for read data that fails validation. The message should name the offending value.
+pub(crate) fn invalid_data(msg: String) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Reads the `u32` length prefix used by length-prefixed types, checking it against the current `ReadConfig`.
+pub(crate) fn read_len<E: Endianness, R: Read>(reader: &mut R) -> Res<usize> {
+	let len: u32 = E::deserialize(reader)?;
+	check_len(len as usize)
+}
+
+/// Reads exactly `len` bytes, growing the buffer as data arrives instead of trusting `len` for the allocation.
+pub(crate) fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Res<Vec<u8>> {
+	let mut buf = vec![];
+	reader.by_ref().take(len as u64).read_to_end(&mut buf)?;
+	if buf.len() != len {
+		return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"));
+	}
+	Ok(buf)
+}
+
+/**
+	Reads a `u32` length prefix, followed by that many elements.
+
+	The elements are collected straight into the boxed slice. The length prefix isn't used to preallocate, so a corrupt prefix results in an `UnexpectedEof` error instead of a huge allocation.
+*/
+impl<E: Endianness, R: Read, D: Deserialize<E, R>> Deserialize<E, R> for Box<[D]> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		(0..len).map(|_| E::deserialize(reader)).collect()
+	}
+}
+
+/// Reads a `u32` length prefix, followed by that many elements, into `Cow::Owned`.
+impl<E: Endianness, R: Read, D: Clone+Deserialize<E, R>> Deserialize<E, R> for Cow<'_, [D]> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let elems: Box<[D]> = Deserialize::<E, R>::deserialize(reader)?;
+		Ok(Cow::Owned(elems.into_vec()))
+	}
+}
+
+/// Reads `N` elements, without a length prefix. The elements are read into the array directly, without allocating.
+impl<E: Endianness, R, D: Deserialize<E, R>, const N: usize> Deserialize<E, R> for [D; N] {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut elems: [Option<D>; N] = std::array::from_fn(|_| None);
+		for elem in &mut elems {
+			*elem = Some(E::deserialize(reader)?);
+		}
+		Ok(elems.map(|elem| elem.expect("every element was read")))
+	}
+}
+
+/// Reads a `u32` length prefix, followed by that many bytes of UTF-8, returning an `InvalidData` error if they aren't valid UTF-8.
+impl<E: Endianness, R: Read> Deserialize<E, R> for String {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		read_string::<E, R, Strict>(reader)
+	}
+}
+
+/// Reads a string in the same format as `String`.
+impl<E: Endianness, R: Read> Deserialize<E, R> for Box<str> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let string: String = Deserialize::<E, R>::deserialize(reader)?;
+		Ok(string.into())
+	}
+}
+
+/// Reads a string in the same format as `String`.
+impl<E: Endianness, R: Read> Deserialize<E, R> for Rc<str> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let string: String = Deserialize::<E, R>::deserialize(reader)?;
+		Ok(string.into())
+	}
+}
+
+/// Reads a string in the same format as `String`.
+impl<E: Endianness, R: Read> Deserialize<E, R> for Arc<str> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let string: String = Deserialize::<E, R>::deserialize(reader)?;
+		Ok(string.into())
+	}
+}
+
+/// Reads a string in the same format as `String`. The result is always valid UTF-8, which every platform can represent.
+impl<E: Endianness, R: Read> Deserialize<E, R> for OsString {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let string: String = Deserialize::<E, R>::deserialize(reader)?;
+		Ok(string.into())
+	}
+}
+
+// todo[specialization]: fall back to Cow::Owned for all other readers
+/**
+	Reads a `u32` length prefix, followed by that many bytes of UTF-8, borrowing them straight from the slice without allocating.
+
+	Returns an `InvalidData` error if the bytes aren't valid UTF-8. As the borrow has to come from the underlying buffer, this is only available when reading from a byte slice. Read a `String` for other readers.
+*/
+impl<'a, E: Endianness> Deserialize<E, &'a [u8]> for Cow<'a, str> {
+	fn deserialize(reader: &mut &'a [u8]) -> Res<Self> {
+		Ok(Cow::Borrowed(DeserializeBorrowed::<E, _>::deserialize_borrowed(reader)?))
+	}
+}
+
+/// The maximum number of entries a map preallocates space for, regardless of its declared count. Use `read_map_capped` to override this.
+pub const MAP_PREALLOC_CAP: usize = 1024;
+
+/// Adds which part of which map entry was being read to the error, keeping its kind.
+fn entry_context(e: io::Error, part: &str, index: usize) -> io::Error {
+	io::Error::new(e.kind(), format!("failed to read {} of map entry {}: {}", part, index, e))
+}
+
+/// Reads a map, preallocating space for at most `max_prealloc` entries so that an untrusted count can't cause a huge allocation up front.
+pub(crate) fn read_map<E: Endianness, R: Read, K: Deserialize<E, R>+Eq+Hash, V: Deserialize<E, R>, S: BuildHasher+Default>(reader: &mut R, max_prealloc: usize) -> Res<HashMap<K, V, S>> {
+	let len = read_len::<E, R>(reader)?;
+	let mut map = HashMap::with_capacity_and_hasher(len.min(max_prealloc), S::default());
+	for i in 0..len {
+		let key = E::deserialize(reader).map_err(|e| entry_context(e, "key", i))?;
+		let value = E::deserialize(reader).map_err(|e| entry_context(e, "value", i))?;
+		map.insert(key, value);
+	}
+	Ok(map)
+}
+
+/// Reads a `u32` length prefix, followed by that many keys and values. If a key occurs more than once, the last value is kept.
+///
+/// Errors name the index of the entry that failed to be read, and whether it was the key or the value.
+///
+/// Preallocates space for at most `MAP_PREALLOC_CAP` entries, growing the map as entries are read.
+impl<E: Endianness, R: Read, K: Deserialize<E, R>+Eq+Hash, V: Deserialize<E, R>, S: BuildHasher+Default> Deserialize<E, R> for HashMap<K, V, S> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		read_map::<E, R, K, V, S>(reader, MAP_PREALLOC_CAP)
+	}
+}
+
+/// Reads a `u32` length prefix, followed by that many keys and values. If a key occurs more than once, the last value is kept.
+impl<E: Endianness, R: Read, K: Deserialize<E, R>+Ord, V: Deserialize<E, R>> Deserialize<E, R> for BTreeMap<K, V> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let mut map = BTreeMap::new();
+		for i in 0..len {
+			let key = E::deserialize(reader).map_err(|e| entry_context(e, "key", i))?;
+			let value = E::deserialize(reader).map_err(|e| entry_context(e, "value", i))?;
+			map.insert(key, value);
+		}
+		Ok(map)
+	}
+}
+
+/// Reads `start`, followed by `end`.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for Range<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let start = E::deserialize(reader)?;
+		let end = E::deserialize(reader)?;
+		Ok(start..end)
+	}
+}
+
+/// Reads `start`.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for RangeFrom<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(E::deserialize(reader)?..)
+	}
+}
+
+/// Reads `end`.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for RangeTo<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(..E::deserialize(reader)?)
+	}
+}
+
+/// Reads nothing.
+impl<E: Endianness, R> Deserialize<E, R> for RangeFull {
+	fn deserialize(_reader: &mut R) -> Res<Self> {
+		Ok(..)
+	}
+}
+
+/// Reads a value and wraps it in a new cell.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for Cell<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Cell::new(E::deserialize(reader)?))
+	}
+}
+
+/// Reads a value and wraps it in a new cell.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for RefCell<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(RefCell::new(E::deserialize(reader)?))
+	}
+}
+
+/// Reads a value and wraps it in a new mutex.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for Mutex<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Mutex::new(E::deserialize(reader)?))
+	}
+}
+
+/// Reads a value and wraps it in a new lock.
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for RwLock<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(RwLock::new(E::deserialize(reader)?))
+	}
+}
+
+/// Reads a value like a `bool` and wraps it in a new atomic. No memory ordering is involved, as the atomic isn't shared yet.
+impl<E: Endianness, R> Deserialize<E, R> for AtomicBool where bool: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(AtomicBool::new(E::deserialize(reader)?))
+	}
+}
+
+/// Reads a `u8` tag, 0 for `Continue` and 1 for `Break`, followed by the variant's value. Returns an `InvalidData` error for any other tag.
+impl<E: Endianness, R: Read, B: Deserialize<E, R>, C: Deserialize<E, R>> Deserialize<E, R> for ControlFlow<B, C> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let tag: u8 = E::deserialize(reader)?;
+		match tag {
+			0 => Ok(ControlFlow::Continue(E::deserialize(reader)?)),
+			1 => Ok(ControlFlow::Break(E::deserialize(reader)?)),
+			_ => Err(invalid_data(format!("ControlFlow had tag {}, expected 0 or 1", tag))),
+		}
+	}
+}
+
+/// Always returns an `InvalidData` error without reading anything, as `Infallible` has no values. This allows generic types to be read when they're instantiated with a variant that can't occur, which is then rejected.
+impl<E: Endianness, R> Deserialize<E, R> for Infallible {
+	fn deserialize(_reader: &mut R) -> Res<Self> {
+		Err(invalid_data("Infallible has no values to read".into()))
+	}
+}
+
+/// Reads an `i8` of -1 for `Less`, 0 for `Equal` and 1 for `Greater`, returning an `InvalidData` error for any other value.
+impl<E: Endianness, R: Read> Deserialize<E, R> for Ordering {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let value: i8 = E::deserialize(reader)?;
+		match value {
+			-1 => Ok(Ordering::Less),
+			0 => Ok(Ordering::Equal),
+			1 => Ok(Ordering::Greater),
+			_ => Err(invalid_data(format!("Ordering had value {}, expected -1, 0 or 1", value))),
+		}
+	}
+}
+
 /// Reads a bool by reading a byte, returning false for 0, true for 1, and an `InvalidData` error for any other value.
 impl<E: Endianness, R: Read> Deserialize<E, R> for bool {
 	fn deserialize(reader: &mut R) -> Res<Self> {
@@ -130,7 +435,7 @@ impl<E: Endianness, R: Read> Deserialize<E, R> for bool {
 		match buf[0] {
 			0 => Ok(false),
 			1 => Ok(true),
-			_ => Err(io::Error::new(io::ErrorKind::InvalidData, "bool had value other than 0 or 1")),
+			value => Err(invalid_data(format!("bool had value {}, expected 0 or 1", value))),
 		}
 	}
 }
@@ -214,11 +519,469 @@ impl<E: Endianness, R: ERead<E>> Deserialize<E, R> for f64 {
 	}
 }
 
+/// Reads a `u32` and converts it to a char, returning an `InvalidData` error if it isn't a Unicode scalar value.
+impl<E: Endianness, R: Read> Deserialize<E, R> for char {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let value: u32 = E::deserialize(reader)?;
+		std::char::from_u32(value).ok_or_else(|| invalid_data(format!("invalid Unicode scalar value {:#X}", value)))
+	}
+}
+
+macro_rules! impl_nonzero {
+	($t:ident, $int:ident) => {
+		/// Reads the underlying integer, returning an `InvalidData` error if it's zero.
+		impl<E: Endianness, R: Read> Deserialize<E, R> for $t {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let value: $int = E::deserialize(reader)?;
+				$t::new(value).ok_or_else(|| invalid_data(format!(concat!(stringify!($t), " had value {}"), value)))
+			}
+		}
+	}
+}
+
+impl_nonzero!(NonZeroU8, u8);
+impl_nonzero!(NonZeroU16, u16);
+impl_nonzero!(NonZeroU32, u32);
+impl_nonzero!(NonZeroU64, u64);
+impl_nonzero!(NonZeroU128, u128);
+impl_nonzero!(NonZeroI8, i8);
+impl_nonzero!(NonZeroI16, i16);
+impl_nonzero!(NonZeroI32, i32);
+impl_nonzero!(NonZeroI64, i64);
+impl_nonzero!(NonZeroI128, i128);
+
+macro_rules! impl_tuple {
+	($($name:ident)+) => {
+		/// Reads the elements in order.
+		impl<E: Endianness, R, $($name: Deserialize<E, R>),+> Deserialize<E, R> for ($($name,)+) {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				Ok(($(E::deserialize::<R, $name>(reader)?,)+))
+			}
+		}
+	}
+}
+
+impl_tuple!(A);
+impl_tuple!(A B);
+impl_tuple!(A B C);
+impl_tuple!(A B C D);
+impl_tuple!(A B C D F);
+impl_tuple!(A B C D F G);
+impl_tuple!(A B C D F G H);
+impl_tuple!(A B C D F G H I);
+impl_tuple!(A B C D F G H I J);
+impl_tuple!(A B C D F G H I J K);
+impl_tuple!(A B C D F G H I J K L);
+impl_tuple!(A B C D F G H I J K L M);
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::excessive_precision, clippy::legacy_numeric_constants)]
 mod tests {
 	use std::io;
 	use std::io::Result as Res;
 
+	#[test]
+	fn read_array() {
+		let mut val: [u16; 2];
+		{
+			use crate::BERead;
+			let mut reader = &b"\xba\xad\xf0\x0d"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, [0xbaad, 0xf00d]);
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\xba\xad\xf0\x0d"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, [0xadba, 0x0df0]);
+		}
+	}
+
+	#[test]
+	fn read_array_of_arrays() {
+		use crate::LERead;
+		let mut reader = &b"\xff\x00\x00\xff\x00\xff\x00\x80\x00\x00\xff\x00"[..];
+		let val: [[u8; 4]; 3] = reader.eread().unwrap();
+		assert_eq!(val, [[0xff, 0x00, 0x00, 0xff], [0x00, 0xff, 0x00, 0x80], [0x00, 0x00, 0xff, 0x00]]);
+	}
+
+	#[test]
+	fn read_array_truncated() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x01a\x00\x00"[..];
+		let err = reader.eread::<[String; 2]>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_boxed_slice() {
+		let mut val: Box<[u32]>;
+		{
+			use crate::BERead;
+			let mut reader = &b"\x00\x00\x00\x02\xba\xad\xf0\x0d\x00\x00\x00\x2a"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(&val[..], [0xbaadf00d, 0x2a]);
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x02\x00\x00\x00\x0d\xf0\xad\xba\x2a\x00\x00\x00"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(&val[..], [0xbaadf00d, 0x2a]);
+		}
+	}
+
+	#[test]
+	fn read_boxed_slice_empty() {
+		use crate::LERead;
+		let mut reader = &b"\x00\x00\x00\x00"[..];
+		let val: Box<[u32]> = reader.eread().unwrap();
+		assert!(val.is_empty());
+	}
+
+	#[test]
+	fn read_boxed_slice_truncated() {
+		use crate::LERead;
+		let mut reader = &b"\xff\xff\xff\xff\x2a\x00\x00\x00"[..];
+		let val = reader.eread::<Box<[u32]>>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_string() {
+		let mut val: String;
+		{
+			use crate::BERead;
+			let mut reader = &b"\x00\x00\x00\x05endio"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, "endio");
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x05\x00\x00\x00endio"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, "endio");
+		}
+	}
+
+	#[test]
+	fn read_string_invalid() {
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\xc3\x28"[..];
+		let val = reader.eread::<String>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+		assert!(val.to_string().contains("from index 0"));
+	}
+
+	#[test]
+	fn read_string_truncated() {
+		use crate::LERead;
+		let mut reader = &b"\xff\xff\xff\xffendio"[..];
+		let val = reader.eread::<String>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_os_string() {
+		use std::ffi::OsString;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x05endio"[..];
+		let val: OsString = reader.eread().unwrap();
+		assert_eq!(val, "endio");
+	}
+
+	#[test]
+	fn read_cow_str_borrowed() {
+		use std::borrow::Cow;
+		let data = b"\x05\x00\x00\x00endio\x2a";
+		use crate::LERead;
+		let mut reader = &data[..];
+		let val: Cow<str> = reader.eread().unwrap();
+		match val {
+			Cow::Borrowed(string) => {
+				assert_eq!(string, "endio");
+				assert_eq!(string.as_ptr(), data[4..].as_ptr());
+			}
+			Cow::Owned(_) => panic!("string was copied"),
+		}
+		assert_eq!(reader, b"\x2a");
+	}
+
+	#[test]
+	fn read_cow_str_invalid() {
+		use std::borrow::Cow;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x02\xc3\x28"[..];
+		let val = reader.eread::<Cow<str>>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+		let mut reader = &b"\x00\x00\x00\x05end"[..];
+		let val = reader.eread::<Cow<str>>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_cow_slice() {
+		use std::borrow::Cow;
+		use crate::{LERead, LEWrite};
+		let borrowed: Cow<[u32]> = Cow::Borrowed(&[0xbaad, 0xf00d]);
+		let owned: Cow<[u32]> = Cow::Owned(vec![42]);
+		let mut writer = vec![];
+		writer.ewrite(&borrowed).unwrap();
+		writer.ewrite(&owned).unwrap();
+		let mut reader = &writer[..];
+		let val: Cow<[u32]> = reader.eread().unwrap();
+		assert!(matches!(val, Cow::Owned(_)));
+		assert_eq!(val, borrowed);
+		let val: Cow<[u32]> = reader.eread().unwrap();
+		assert_eq!(val, owned);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn read_shared_str() {
+		use std::rc::Rc;
+		use std::sync::Arc;
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(&String::from("endio")).unwrap();
+		let mut reader = &writer[..];
+		let val: Box<str> = reader.eread().unwrap();
+		assert_eq!(&*val, "endio");
+		let mut reader = &writer[..];
+		let val: Rc<str> = reader.eread().unwrap();
+		assert_eq!(&*val, "endio");
+		let mut reader = &writer[..];
+		let val: Arc<str> = reader.eread().unwrap();
+		assert_eq!(&*val, "endio");
+	}
+
+	#[test]
+	fn read_btree_map() {
+		use std::collections::BTreeMap;
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\x02\x02\x00\x00\x00io\x01\x03\x00\x00\x00end"[..];
+		let val: BTreeMap<u8, String> = reader.eread().unwrap();
+		assert_eq!(val.into_iter().collect::<Vec<_>>(), [(1, String::from("end")), (2, String::from("io"))]);
+	}
+
+	#[test]
+	fn read_hash_map() {
+		use std::collections::HashMap;
+		let mut val: HashMap<u16, String>;
+		{
+			use crate::BERead;
+			let mut reader = &b"\x00\x00\x00\x01\xba\xad\x00\x00\x00\x05endio"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val.len(), 1);
+			assert_eq!(val[&0xbaad], "endio");
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x01\x00\x00\x00\xad\xba\x05\x00\x00\x00endio"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val.len(), 1);
+			assert_eq!(val[&0xbaad], "endio");
+		}
+	}
+
+	#[test]
+	fn hash_map_enum_keys() {
+		use std::collections::HashMap;
+		use std::io::{Read, Write};
+		use crate::{Deserialize, Endianness, LERead, LEWrite, Serialize};
+
+		#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+		enum Kind {
+			Read = 1,
+			Write = 2,
+		}
+
+		impl<E: Endianness, W: Write> Serialize<E, W> for &Kind {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				E::serialize(*self as u8, writer)
+			}
+		}
+
+		impl<E: Endianness, R: Read> Deserialize<E, R> for Kind {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let tag: u8 = E::deserialize(reader)?;
+				match tag {
+					1 => Ok(Kind::Read),
+					2 => Ok(Kind::Write),
+					_ => Err(super::invalid_data(format!("Kind had tag {}", tag))),
+				}
+			}
+		}
+
+		let mut map = HashMap::new();
+		map.insert(Kind::Read, 0xbaadu32);
+		map.insert(Kind::Write, 0xf00d);
+		let mut writer = vec![];
+		writer.ewrite(&map).unwrap();
+		assert_eq!(writer.len(), 4 + 2 * 5);
+		let mut reader = &writer[..];
+		let val: HashMap<Kind, u32> = reader.eread().unwrap();
+		assert_eq!(val, map);
+	}
+
+	#[test]
+	fn read_hash_map_huge_count() {
+		use std::collections::HashMap;
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\xff\xff\xff\xff\xad\xba\x2a"[..];
+		let err = LERead::eread::<HashMap<u16, u8>>(&mut reader).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_hash_map_entry_error() {
+		use std::collections::{BTreeMap, HashMap};
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x03\x01\x00\x02\x01\x03\x02"[..];
+		let err = reader.eread::<HashMap<u8, bool>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert!(err.to_string().starts_with("failed to read value of map entry 2: "));
+		let mut reader = &b"\x00\x00\x00\x02\x01\x00\x02"[..];
+		let err = reader.eread::<BTreeMap<u8, bool>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		assert!(err.to_string().starts_with("failed to read value of map entry 1: "));
+	}
+
+	#[test]
+	fn read_ranges() {
+		use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+		use crate::LERead;
+		let mut reader = &b"\xad\xba\x0d\xf0\xad\xba\x0d\xf0"[..];
+		let range: Range<u16> = reader.eread().unwrap();
+		assert_eq!(range, 0xbaad..0xf00d);
+		let from: RangeFrom<u16> = reader.eread().unwrap();
+		assert_eq!(from, 0xbaad..);
+		let full: RangeFull = reader.eread().unwrap();
+		assert_eq!(full, ..);
+		assert_eq!(reader.len(), 2);
+		let to: RangeTo<u16> = reader.eread().unwrap();
+		assert_eq!(to, ..0xf00d);
+		let _: RangeFull = reader.eread().unwrap();
+	}
+
+	#[test]
+	fn read_cells() {
+		use std::cell::{Cell, RefCell};
+		use crate::LERead;
+		let mut reader = &b"\xad\xba\x0d\xf0"[..];
+		let cell: Cell<u16> = reader.eread().unwrap();
+		assert_eq!(cell.get(), 0xbaad);
+		let ref_cell: RefCell<u16> = reader.eread().unwrap();
+		assert_eq!(*ref_cell.borrow(), 0xf00d);
+	}
+
+	#[test]
+	fn read_mutex() {
+		use std::sync::{Mutex, RwLock};
+		use crate::BERead;
+		let mut reader = &b"\xba\xad\xf0\x0d\xba\xad"[..];
+		let mutex: Mutex<u32> = reader.eread().unwrap();
+		assert_eq!(*mutex.lock().unwrap(), 0xbaadf00d);
+		let lock: RwLock<u16> = reader.eread().unwrap();
+		assert_eq!(*lock.read().unwrap(), 0xbaad);
+	}
+
+	#[test]
+	fn read_control_flow() {
+		use std::ops::ControlFlow;
+		let mut val: ControlFlow<u16, u16>;
+		{
+			use crate::BERead;
+			let mut reader = &b"\x00\xba\xad\x01\xba\xad"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, ControlFlow::Continue(0xbaad));
+			val = reader.eread().unwrap();
+			assert_eq!(val, ControlFlow::Break(0xbaad));
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x00\xad\xba\x01\xad\xba"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, ControlFlow::Continue(0xbaad));
+			val = reader.eread().unwrap();
+			assert_eq!(val, ControlFlow::Break(0xbaad));
+		}
+	}
+
+	#[test]
+	fn read_control_flow_invalid() {
+		use std::ops::ControlFlow;
+		use crate::LERead;
+		let mut reader = &b"\x02\xad\xba"[..];
+		let val = reader.eread::<ControlFlow<u16, u16>>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn read_infallible_variant() {
+		use std::convert::Infallible;
+		use std::ops::ControlFlow;
+		use crate::BERead;
+		let mut reader = &b"\x00\xba\xad\x01\xba\xad"[..];
+		let val: ControlFlow<Infallible, u16> = reader.eread().unwrap();
+		assert_eq!(val, ControlFlow::Continue(0xbaad));
+		let err = reader.eread::<ControlFlow<Infallible, u16>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "Infallible has no values to read");
+		assert_eq!(reader, b"\xba\xad");
+	}
+
+	#[test]
+	fn read_tuple() {
+		let mut val: (u8, u16, bool);
+		{
+			use crate::BERead;
+			let mut reader = &b"\x2a\xba\xad\x01"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, (0x2a, 0xbaad, true));
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x2a\xad\xba\x01"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, (0x2a, 0xbaad, true));
+		}
+	}
+
+	#[test]
+	fn read_ordering() {
+		use std::cmp::Ordering;
+		let mut val: Ordering;
+		{
+			use crate::BERead;
+			let mut reader = &b"\xff\x00\x01"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, Ordering::Less);
+			val = reader.eread().unwrap();
+			assert_eq!(val, Ordering::Equal);
+			val = reader.eread().unwrap();
+			assert_eq!(val, Ordering::Greater);
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\xff"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, Ordering::Less);
+		}
+	}
+
+	#[test]
+	fn read_ordering_invalid() {
+		use std::cmp::Ordering;
+		use crate::LERead;
+		let mut reader = &b"\x02"[..];
+		let val = reader.eread::<Ordering>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+		assert!(val.to_string().contains("value 2"));
+	}
+
 	#[test]
 	fn read_bool_false() {
 		let data = b"\x00";
@@ -273,6 +1036,68 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn read_bool_invalid_message() {
+		use crate::LERead;
+		let mut reader = &b"\x2a"[..];
+		let val = reader.eread::<bool>().unwrap_err();
+		assert!(val.to_string().contains("42"));
+	}
+
+	#[test]
+	fn read_char() {
+		let mut val: char;
+		{
+			use crate::BERead;
+			let mut reader = &b"\x00\x01\xf9\x80"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, '\u{1f980}');
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\x80\xf9\x01\x00"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val, '\u{1f980}');
+		}
+	}
+
+	#[test]
+	fn read_char_invalid() {
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\xd8\x00"[..];
+		let val = reader.eread::<char>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+		assert!(val.to_string().contains("0xD800"));
+	}
+
+	#[test]
+	fn read_nonzero() {
+		use std::num::NonZeroU16;
+		let mut val: NonZeroU16;
+		{
+			use crate::BERead;
+			let mut reader = &b"\xba\xad"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val.get(), 0xbaad);
+		}
+		{
+			use crate::LERead;
+			let mut reader = &b"\xad\xba"[..];
+			val = reader.eread().unwrap();
+			assert_eq!(val.get(), 0xbaad);
+		}
+	}
+
+	#[test]
+	fn read_nonzero_zero() {
+		use std::num::NonZeroI32;
+		use crate::LERead;
+		let mut reader = &b"\x00\x00\x00\x00"[..];
+		let val = reader.eread::<NonZeroI32>().unwrap_err();
+		assert_eq!(val.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(val.to_string(), "NonZeroI32 had value 0");
+	}
+
 	#[test]
 	fn read_i8() {
 		let data = b"\x80";
@@ -345,6 +1170,24 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn read_f32_forced() {
+		use crate::BERead;
+		let mut reader = &b"\x44\x20\xa7\x44\x44\x20\xa7\x44"[..];
+		let be: f32 = reader.eread().unwrap();
+		let le: f32 = reader.read_le().unwrap();
+		assert_eq!(be, 642.6135);
+		assert_eq!(le, 1337.0083);
+	}
+
+	#[test]
+	fn read_f64_forced() {
+		use crate::LERead;
+		let mut reader = &b"\x40\x94\x7a\x14\xae\xe5\x94\x40"[..];
+		let val: f64 = reader.read_be().unwrap();
+		assert_eq!(val, 1310.5201984283194);
+	}
+
 	#[test]
 	fn read_struct_forced() {
 		struct Test {
@@ -0,0 +1,143 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::Result as Res;
+use std::time::Duration;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+macro_rules! impl_duration {
+	($name:ident, $unit:literal, $as:ident, $from:ident) => {
+		#[doc = concat!("A `Duration` read/written as a single `u64` of ", $unit, ".")]
+		///
+		#[doc = concat!("Writing truncates any remainder smaller than ", $unit, ", and returns an `InvalidInput` error if the duration doesn't fit in a `u64` of ", $unit, ".")]
+		#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		pub struct $name(pub Duration);
+
+		impl<E: Endianness, W> Serialize<E, W> for $name where u64: Serialize<E, W> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				let value = u64::try_from(self.0.$as()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, concat!("duration too large for u64 ", $unit)))?;
+				E::serialize(value, writer)
+			}
+		}
+
+		impl<E: Endianness, R> Deserialize<E, R> for $name where u64: Deserialize<E, R> {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let value: u64 = E::deserialize(reader)?;
+				Ok(Self(Duration::$from(value)))
+			}
+		}
+	}
+}
+
+impl_duration!(DurationMillis, "milliseconds", as_millis, from_millis);
+impl_duration!(DurationMicros, "microseconds", as_micros, from_micros);
+
+/// A `Duration` read/written as a single `f64` of seconds.
+///
+/// Writing is lossy for durations that need more precision than an `f64` has. Reading returns an `InvalidData` error for negative, non-finite or too large values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationSecsF64(pub Duration);
+
+impl<E: Endianness, W> Serialize<E, W> for DurationSecsF64 where f64: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0.as_secs_f64(), writer)
+	}
+}
+
+impl<E: Endianness, R> Deserialize<E, R> for DurationSecsF64 where f64: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let secs: f64 = E::deserialize(reader)?;
+		Duration::try_from_secs_f64(secs).map(Self).map_err(|e| invalid_data(format!("{} seconds is not a valid duration: {}", secs, e)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+	use std::time::Duration;
+
+	use super::{DurationMicros, DurationMillis, DurationSecsF64};
+
+	#[test]
+	fn millis() {
+		let val = DurationMillis(Duration::from_millis(0xbaad));
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x00\x00\x00\xba\xad");
+			let mut reader = &writer[..];
+			let read: DurationMillis = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\xad\xba\x00\x00\x00\x00\x00\x00");
+			let mut reader = &writer[..];
+			let read: DurationMillis = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+	}
+
+	#[test]
+	fn millis_truncated() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(DurationMillis(Duration::new(1, 999_999))).unwrap();
+		let mut reader = &writer[..];
+		let read: DurationMillis = reader.eread().unwrap();
+		assert_eq!(read.0, Duration::from_millis(1000));
+	}
+
+	#[test]
+	fn micros() {
+		use crate::{LERead, LEWrite};
+		let val = DurationMicros(Duration::from_micros(u64::MAX));
+		let mut writer = vec![];
+		writer.ewrite(val).unwrap();
+		assert_eq!(writer, b"\xff\xff\xff\xff\xff\xff\xff\xff");
+		let mut reader = &writer[..];
+		let read: DurationMicros = reader.eread().unwrap();
+		assert_eq!(read, val);
+	}
+
+	#[test]
+	fn micros_overflow() {
+		use crate::LEWrite;
+		let val = DurationMicros(Duration::from_micros(u64::MAX) + Duration::from_micros(1));
+		let mut writer = vec![];
+		let err = writer.ewrite(val).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(writer.is_empty());
+	}
+
+	#[test]
+	fn secs_f64() {
+		use crate::{BERead, BEWrite};
+		let val = DurationSecsF64(Duration::from_millis(1500));
+		let mut writer = vec![];
+		writer.ewrite(val).unwrap();
+		assert_eq!(writer, 1.5f64.to_be_bytes());
+		let mut reader = &writer[..];
+		let read: DurationSecsF64 = reader.eread().unwrap();
+		assert_eq!(read, val);
+	}
+
+	#[test]
+	fn secs_f64_invalid() {
+		use crate::LERead;
+		for secs in [-1.5, f64::NAN, f64::INFINITY, 1e30] {
+			let bytes = f64::to_le_bytes(secs);
+			let mut reader = &bytes[..];
+			let err = reader.eread::<DurationSecsF64>().unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		}
+		let bytes = (-1.5f64).to_le_bytes();
+		let mut reader = &bytes[..];
+		let err = reader.eread::<DurationSecsF64>().unwrap_err();
+		assert_eq!(err.to_string(), "-1.5 seconds is not a valid duration: cannot convert float seconds to Duration: value is negative");
+	}
+}
@@ -9,6 +9,11 @@ use std::io;
 	You can't implement this trait, it only exists as a trait bound.
 */
 pub trait Endianness: Sized + private::Sealed {
+	/// `"big"` or `"little"`, for diagnostics in generic code.
+	const NAME: &'static str;
+	/// Whether this is `LittleEndian`.
+	const IS_LITTLE: bool;
+
 	fn serialize<W, S: Serialize<Self, W>>(value: S, writer: &mut W) -> io::Result<()>;
 	fn deserialize<R, D: Deserialize<Self, R>>(reader: &mut R) -> io::Result<D>;
 }
@@ -26,7 +31,22 @@ pub struct BigEndian;
 */
 pub struct LittleEndian;
 
+/**
+	The endianness chosen for the whole build: `BigEndian` if the `default-big-endian` feature is enabled, `LittleEndian` otherwise.
+
+	Use this in code that should follow a single crate-wide wire format, so that every binary built with the same features agrees on it. Since enabling the feature changes this type for every crate in the build, only enable it from the final binary.
+*/
+pub type DefaultEndian = SelectedEndian;
+
+#[cfg(feature = "default-big-endian")]
+type SelectedEndian = BigEndian;
+#[cfg(not(feature = "default-big-endian"))]
+type SelectedEndian = LittleEndian;
+
 impl Endianness for BigEndian {
+	const NAME: &'static str = "big";
+	const IS_LITTLE: bool = false;
+
 	fn serialize<W, S: Serialize<Self, W>>(value: S, writer: &mut W) -> io::Result<()> {
 		value.serialize_be(writer)
 	}
@@ -37,6 +57,9 @@ impl Endianness for BigEndian {
 }
 
 impl Endianness for LittleEndian {
+	const NAME: &'static str = "little";
+	const IS_LITTLE: bool = true;
+
 	fn serialize<W, S: Serialize<Self, W>>(value: S, writer: &mut W) -> io::Result<()> {
 		value.serialize_le(writer)
 	}
@@ -46,6 +69,42 @@ impl Endianness for LittleEndian {
 	}
 }
 
+/**
+	Writes the value in the endianness `E`, without needing a `*Write` trait in scope.
+
+	## Examples
+
+	```
+	use endio::BigEndian;
+
+	let mut writer = vec![];
+	endio::serialize::<BigEndian, _>(0xbaadu16, &mut writer).unwrap();
+	assert_eq!(writer, b"\xba\xad");
+	```
+*/
+pub fn serialize<E: Endianness, W>(value: impl Serialize<E, W>, writer: &mut W) -> io::Result<()> {
+	E::serialize(value, writer)
+}
+
+/**
+	Reads a value in the endianness `E`, without needing a `*Read` trait in scope.
+
+	The reader's type has to be given as well when naming the value type, as in `deserialize::<E, D, _>`, because the bound on `D` refers to it. When the value type can be inferred, `deserialize::<E, _, _>` works too.
+
+	## Examples
+
+	```
+	use endio::LittleEndian;
+
+	let mut reader = &b"\xad\xba"[..];
+	let value = endio::deserialize::<LittleEndian, u16, _>(&mut reader).unwrap();
+	assert_eq!(value, 0xbaad);
+	```
+*/
+pub fn deserialize<E: Endianness, D: Deserialize<E, R>, R>(reader: &mut R) -> io::Result<D> {
+	E::deserialize(reader)
+}
+
 // ensures no one else implements the trait
 mod private {
 	pub trait Sealed {}
@@ -53,3 +112,36 @@ mod private {
 	impl Sealed for super::BigEndian {}
 	impl Sealed for super::LittleEndian {}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{BigEndian, DefaultEndian, Endianness, LittleEndian};
+
+	fn describe<E: Endianness>() -> String {
+		format!("{} endian (little: {})", E::NAME, E::IS_LITTLE)
+	}
+
+	#[test]
+	fn default_endian() {
+		assert_eq!(DefaultEndian::IS_LITTLE, !cfg!(feature = "default-big-endian"));
+	}
+
+	#[test]
+	fn free_functions() {
+		let mut writer = vec![];
+		super::serialize::<BigEndian, _>(0xbaadf00du32, &mut writer).unwrap();
+		super::serialize::<LittleEndian, _>(0xbaadf00du32, &mut writer).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d\x0d\xf0\xad\xba");
+		let mut reader = &writer[..];
+		assert_eq!(super::deserialize::<BigEndian, u32, _>(&mut reader).unwrap(), 0xbaadf00d);
+		assert_eq!(super::deserialize::<LittleEndian, u32, _>(&mut reader).unwrap(), 0xbaadf00d);
+	}
+
+	#[test]
+	fn name() {
+		assert_eq!(BigEndian::NAME, "big");
+		assert_eq!(LittleEndian::NAME, "little");
+		assert_eq!(describe::<BigEndian>(), "big endian (little: false)");
+		assert_eq!(describe::<LittleEndian>(), "little endian (little: true)");
+	}
+}
@@ -0,0 +1,73 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use arrayvec::ArrayString;
+
+use crate::{Deserialize, Endianness, PolicyString, Serialize, Strict, StringPolicy};
+use crate::deserialize::{invalid_data, read_bytes, read_len};
+
+/// Writes the string in the same format as `&str`.
+impl<E: Endianness, W: Write, const N: usize> Serialize<E, W> for &ArrayString<N> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.as_str(), writer)
+	}
+}
+
+/// Reads a string in the same format as `String`. Returns an `InvalidData` error if it's longer than the capacity `N`, without reading the string itself.
+impl<E: Endianness, R: Read, const N: usize> Deserialize<E, R> for ArrayString<N> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		E::deserialize::<R, PolicyString<Strict, Self>>(reader).map(PolicyString::into_inner)
+	}
+}
+
+/// Decodes the string with the policy `P`. Returns an `InvalidData` error if it's longer than the capacity `N`, either as read or after decoding.
+impl<E: Endianness, R: Read, P: StringPolicy, const N: usize> Deserialize<E, R> for PolicyString<P, ArrayString<N>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		if len > N {
+			return Err(invalid_data(format!("string of length {} exceeds capacity {}", len, N)));
+		}
+		let string = P::decode(read_bytes(reader, len)?)?;
+		ArrayString::from(&string)
+			.map(PolicyString::new)
+			.map_err(|_| invalid_data(format!("decoded string of length {} exceeds capacity {}", string.len(), N)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use arrayvec::ArrayString;
+
+	#[test]
+	fn fits() {
+		use crate::{LERead, LEWrite};
+		let string = ArrayString::<8>::from("endio").unwrap();
+		let mut writer = vec![];
+		writer.ewrite(&string).unwrap();
+		assert_eq!(writer, b"\x05\x00\x00\x00endio");
+		let mut reader = &writer[..];
+		let val: ArrayString<5> = reader.eread().unwrap();
+		assert_eq!(val.as_str(), "endio");
+	}
+
+	#[test]
+	fn exceeds_capacity() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x05endio"[..];
+		let err = reader.eread::<ArrayString<4>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "string of length 5 exceeds capacity 4");
+	}
+
+	#[test]
+	fn lossy() {
+		use crate::{LERead, Lossy, PolicyString};
+		let mut reader = &b"\x03\x00\x00\x00a\xffb\x03\x00\x00\x00a\xffb"[..];
+		let val: PolicyString<Lossy, ArrayString<5>> = reader.eread().unwrap();
+		assert_eq!(val.0.as_str(), "a\u{fffd}b");
+		let err = reader.eread::<PolicyString<Lossy, ArrayString<3>>>().unwrap_err();
+		assert_eq!(err.to_string(), "decoded string of length 5 exceeds capacity 3");
+	}
+}
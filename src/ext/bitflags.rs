@@ -0,0 +1,129 @@
+use std::fmt::LowerHex;
+use std::io::Result as Res;
+
+use bitflags::Flags;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/**
+	Reads and writes a `bitflags` flag set as its underlying integer.
+
+	Reading returns an `InvalidData` error if any bits are set that don't correspond to a defined flag. Use `BitFlagsRetain` to keep unknown bits instead.
+
+	## Examples
+
+	```
+	use endio::{BitFlags, LERead, LEWrite};
+
+	bitflags::bitflags! {
+		#[derive(Debug, PartialEq)]
+		struct Perms: u16 {
+			const READ = 1;
+			const WRITE = 2;
+		}
+	}
+
+	let mut writer = vec![];
+	writer.ewrite(BitFlags(Perms::READ | Perms::WRITE)).unwrap();
+	assert_eq!(writer, b"\x03\x00");
+
+	let mut reader = &b"\x04\x00"[..];
+	assert!(reader.eread::<BitFlags<Perms>>().is_err());
+	```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitFlags<T>(pub T);
+
+/// Reads and writes a `bitflags` flag set as its underlying integer, retaining any unknown bits when reading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitFlagsRetain<T>(pub T);
+
+impl<E: Endianness, W, T: Flags> Serialize<E, W> for BitFlags<T> where T::Bits: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0.bits(), writer)
+	}
+}
+
+impl<E: Endianness, R, T: Flags> Deserialize<E, R> for BitFlags<T> where T::Bits: Deserialize<E, R>+LowerHex {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let bits = E::deserialize(reader)?;
+		match T::from_bits(bits) {
+			Some(flags) => Ok(BitFlags(flags)),
+			None => Err(invalid_data(format!("flags had unknown bits set in {:#x}", bits & !T::all().bits()))),
+		}
+	}
+}
+
+impl<E: Endianness, W, T: Flags> Serialize<E, W> for BitFlagsRetain<T> where T::Bits: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0.bits(), writer)
+	}
+}
+
+impl<E: Endianness, R, T: Flags> Deserialize<E, R> for BitFlagsRetain<T> where T::Bits: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let bits = E::deserialize(reader)?;
+		Ok(BitFlagsRetain(T::from_bits_retain(bits)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::{BitFlags, BitFlagsRetain};
+
+	bitflags::bitflags! {
+		#[derive(Clone, Copy, Debug, PartialEq)]
+		struct Perms: u32 {
+			const READ = 1;
+			const WRITE = 2;
+			const EXEC = 4;
+		}
+	}
+
+	#[test]
+	fn flags_valid() {
+		let val = Perms::READ | Perms::EXEC;
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(BitFlags(val)).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x05");
+			let mut reader = &writer[..];
+			let read: BitFlags<Perms> = reader.eread().unwrap();
+			assert_eq!(read.0, val);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(BitFlags(val)).unwrap();
+			assert_eq!(writer, b"\x05\x00\x00\x00");
+			let mut reader = &writer[..];
+			let read: BitFlags<Perms> = reader.eread().unwrap();
+			assert_eq!(read.0, val);
+		}
+	}
+
+	#[test]
+	fn flags_unknown_rejected() {
+		use crate::LERead;
+		let mut reader = &b"\x09\x00\x00\x00"[..];
+		let err = reader.eread::<BitFlags<Perms>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert!(err.to_string().contains("0x8"));
+	}
+
+	#[test]
+	fn flags_unknown_retained() {
+		use crate::{LERead, LEWrite};
+		let mut reader = &b"\x09\x00\x00\x00"[..];
+		let read: BitFlagsRetain<Perms> = reader.eread().unwrap();
+		assert_eq!(read.0.bits(), 9);
+		assert!(read.0.contains(Perms::READ));
+		let mut writer = vec![];
+		writer.ewrite(read).unwrap();
+		assert_eq!(writer, b"\x09\x00\x00\x00");
+	}
+}
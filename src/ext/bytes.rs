@@ -0,0 +1,127 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{read_bytes, read_len};
+use crate::serialize::write_len;
+
+/// Writes a `u32` length prefix, followed by the bytes in a single `write_all`. `BufMut::put_slice` isn't used, since the writer is a generic `Write` rather than a `BufMut`.
+impl<E: Endianness, W: Write> Serialize<E, W> for &Bytes {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		writer.write_all(self)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the bytes in a single `write_all`, as for `Bytes`.
+impl<E: Endianness, W: Write> Serialize<E, W> for &BytesMut {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		writer.write_all(self)
+	}
+}
+
+/**
+	Reads a `u32` length prefix, followed by that many bytes.
+
+	`Buf::copy_to_bytes` isn't used, since the reader is a generic `Read` rather than a `Buf`, and can't hand out its underlying buffer to share. Instead, the bytes are read into a fresh buffer once, which is then taken over without any further copies. The buffer grows as bytes arrive instead of trusting the length prefix for the allocation, like when reading a `Box<[u8]>`.
+*/
+impl<E: Endianness, R: Read> Deserialize<E, R> for Bytes {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		Ok(Bytes::from(read_bytes(reader, len)?))
+	}
+}
+
+/// Reads a `u32` length prefix, followed by that many bytes. As for `Bytes`, the bytes are read into a buffer that grows as they arrive instead of trusting the length prefix for the allocation, and are then copied into the `BytesMut`.
+impl<E: Endianness, R: Read> Deserialize<E, R> for BytesMut {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		Ok(BytesMut::from(&read_bytes(reader, len)?[..]))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Result as Res;
+	use std::io::Write;
+
+	use bytes::{Bytes, BytesMut};
+
+	struct CallCounter {
+		data: Vec<u8>,
+		calls: usize,
+	}
+
+	impl Write for CallCounter {
+		fn write(&mut self, buf: &[u8]) -> Res<usize> {
+			self.calls += 1;
+			self.data.write(buf)
+		}
+
+		fn flush(&mut self) -> Res<()> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn bytes_round_trip() {
+		let val = Bytes::from_static(b"\xba\xad\xf0\x0d");
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(&val).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x04\xba\xad\xf0\x0d");
+			let mut reader = &writer[..];
+			let read: Bytes = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(&val).unwrap();
+			assert_eq!(writer, b"\x04\x00\x00\x00\xba\xad\xf0\x0d");
+			let mut reader = &writer[..];
+			let read: Bytes = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+	}
+
+	#[test]
+	fn bytes_mut_round_trip() {
+		use crate::{LERead, LEWrite};
+		let val = BytesMut::from(&b"\xba\xad"[..]);
+		let mut writer = vec![];
+		writer.ewrite(&val).unwrap();
+		assert_eq!(writer, b"\x02\x00\x00\x00\xba\xad");
+		let mut reader = &writer[..];
+		let read: BytesMut = reader.eread().unwrap();
+		assert_eq!(read, val);
+	}
+
+	#[test]
+	fn bytes_single_write_all() {
+		use crate::LEWrite;
+		let val = Bytes::from(vec![0x2a; 4096]);
+		let mut writer = CallCounter { data: vec![], calls: 0 };
+		writer.ewrite(&val).unwrap();
+		// one call for the length prefix, one for the entire payload
+		assert_eq!(writer.calls, 2);
+		assert_eq!(writer.data.len(), 4 + 4096);
+	}
+
+	#[test]
+	fn huge_count() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\xff\xff\xff\xff\xba\xad"[..];
+		let err = reader.eread::<BytesMut>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		let mut reader = &b"\xff\xff\xff\xff\xba\xad"[..];
+		let err = reader.eread::<Bytes>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
@@ -0,0 +1,64 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/// Writes the time in UTC, as the `i64` seconds since the Unix epoch, followed by the `u32` nanoseconds within that second. The nanoseconds can be 1_000_000_000 or more for a leap second.
+impl<E: Endianness, W: Write, Tz: TimeZone> Serialize<E, W> for DateTime<Tz> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.timestamp(), writer)?;
+		E::serialize(self.timestamp_subsec_nanos(), writer)
+	}
+}
+
+/// Reads the format written by `DateTime`'s `Serialize`. Returns an `InvalidData` error if the time is out of `chrono`'s range or the nanoseconds are invalid.
+impl<E: Endianness, R: Read> Deserialize<E, R> for DateTime<Utc> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let secs: i64 = E::deserialize(reader)?;
+		let nanos: u32 = E::deserialize(reader)?;
+		DateTime::from_timestamp(secs, nanos).ok_or_else(|| invalid_data(format!("timestamp of {}s and {}ns is out of range", secs, nanos)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+	#[test]
+	fn round_trip() {
+		use crate::{BERead, BEWrite};
+		let time = Utc.with_ymd_and_hms(2024, 2, 29, 13, 37, 0).unwrap() + chrono::Duration::nanoseconds(42);
+		let mut writer = vec![];
+		writer.ewrite(time).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00\x65\xe0\x88\x7c\x00\x00\x00\x2a");
+		let mut reader = &writer[..];
+		let val: DateTime<Utc> = reader.eread().unwrap();
+		assert_eq!(val, time);
+	}
+
+	#[test]
+	fn offset_written_as_utc() {
+		use crate::LEWrite;
+		let offset = FixedOffset::east_opt(3600).unwrap();
+		let time = offset.with_ymd_and_hms(2024, 2, 29, 14, 37, 0).unwrap();
+		let mut with_offset = vec![];
+		with_offset.ewrite(time).unwrap();
+		let mut utc = vec![];
+		utc.ewrite(time.with_timezone(&Utc)).unwrap();
+		assert_eq!(with_offset, utc);
+	}
+
+	#[test]
+	fn invalid_nanos() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x94\x35\x77"[..];
+		let err = reader.eread::<DateTime<Utc>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "timestamp of 0s and 2000000000ns is out of range");
+	}
+}
@@ -0,0 +1,73 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use either::Either;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/// Writes a `u8` tag, 0 for `Left` and 1 for `Right`, followed by the side's value.
+impl<E: Endianness, W: Write, A: Serialize<E, W>, B: Serialize<E, W>> Serialize<E, W> for Either<A, B> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		match self {
+			Either::Left(value) => {
+				E::serialize(0u8, writer)?;
+				E::serialize(value, writer)
+			}
+			Either::Right(value) => {
+				E::serialize(1u8, writer)?;
+				E::serialize(value, writer)
+			}
+		}
+	}
+}
+
+/// Reads a `u8` tag, 0 for `Left` and 1 for `Right`, followed by the side's value. Returns an `InvalidData` error for any other tag.
+impl<E: Endianness, R: Read, A: Deserialize<E, R>, B: Deserialize<E, R>> Deserialize<E, R> for Either<A, B> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let tag: u8 = E::deserialize(reader)?;
+		match tag {
+			0 => Ok(Either::Left(E::deserialize(reader)?)),
+			1 => Ok(Either::Right(E::deserialize(reader)?)),
+			_ => Err(invalid_data(format!("Either had tag {}, expected 0 or 1", tag))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use either::Either;
+
+	#[test]
+	fn left() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(Either::<u16, bool>::Left(0xbaad)).unwrap();
+		assert_eq!(writer, b"\x00\xba\xad");
+		let mut reader = &writer[..];
+		let val: Either<u16, bool> = reader.eread().unwrap();
+		assert_eq!(val, Either::Left(0xbaad));
+	}
+
+	#[test]
+	fn right() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(Either::<u16, u32>::Right(0xbaadf00d)).unwrap();
+		assert_eq!(writer, b"\x01\x0d\xf0\xad\xba");
+		let mut reader = &writer[..];
+		let val: Either<u16, u32> = reader.eread().unwrap();
+		assert_eq!(val, Either::Right(0xbaadf00d));
+	}
+
+	#[test]
+	fn bad_tag() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x02\x2a"[..];
+		let err = reader.eread::<Either<u8, u8>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "Either had tag 2, expected 0 or 1");
+	}
+}
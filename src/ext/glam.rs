@@ -0,0 +1,74 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use glam::{DQuat, DVec2, DVec3, DVec4, Quat, Vec2, Vec3, Vec4};
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/// Implements `Serialize` and `Deserialize` for a glam type as its components in field order.
+macro_rules! impl_components {
+	($t:ty, $scalar:ty, $new:path, $($field:ident),+) => {
+		impl<E: Endianness, W: Write> Serialize<E, W> for $t {
+			const SIZE: Option<usize> = Some(std::mem::size_of::<$scalar>() * [$(stringify!($field)),+].len());
+
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				$(E::serialize(self.$field, writer)?;)+
+				Ok(())
+			}
+		}
+
+		impl<E: Endianness, W: Write> Serialize<E, W> for &$t {
+			const SIZE: Option<usize> = <$t as Serialize<E, W>>::SIZE;
+
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				E::serialize(*self, writer)
+			}
+		}
+
+		impl<E: Endianness, R: Read> Deserialize<E, R> for $t {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				$(let $field: $scalar = E::deserialize(reader)?;)+
+				Ok($new($($field),+))
+			}
+		}
+	}
+}
+
+impl_components!(Vec2, f32, Vec2::new, x, y);
+impl_components!(Vec3, f32, Vec3::new, x, y, z);
+impl_components!(Vec4, f32, Vec4::new, x, y, z, w);
+impl_components!(Quat, f32, Quat::from_xyzw, x, y, z, w);
+impl_components!(DVec2, f64, DVec2::new, x, y);
+impl_components!(DVec3, f64, DVec3::new, x, y, z);
+impl_components!(DVec4, f64, DVec4::new, x, y, z, w);
+impl_components!(DQuat, f64, DQuat::from_xyzw, x, y, z, w);
+
+#[cfg(test)]
+mod tests {
+	use glam::{Quat, Vec3};
+
+	#[test]
+	fn vec3() {
+		use crate::{BERead, BEWrite};
+		let vec = Vec3::new(1.0, 2.0, -0.5);
+		let mut writer = vec![];
+		writer.ewrite(vec).unwrap();
+		assert_eq!(writer, b"\x3f\x80\x00\x00\x40\x00\x00\x00\xbf\x00\x00\x00");
+		let mut reader = &writer[..];
+		let val: Vec3 = reader.eread().unwrap();
+		assert_eq!(val, vec);
+	}
+
+	#[test]
+	fn quat() {
+		use crate::{LERead, LEWrite};
+		let quat = Quat::from_xyzw(1.0, 2.0, -0.5, 0.0);
+		let mut writer = vec![];
+		writer.ewrite(quat).unwrap();
+		assert_eq!(writer, b"\x00\x00\x80\x3f\x00\x00\x00\x40\x00\x00\x00\xbf\x00\x00\x00\x00");
+		let mut reader = &writer[..];
+		let val: Quat = reader.eread().unwrap();
+		assert_eq!(val, quat);
+	}
+}
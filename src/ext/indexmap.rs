@@ -0,0 +1,90 @@
+use std::hash::{BuildHasher, Hash};
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{Deserialize, Endianness, MAP_PREALLOC_CAP, Serialize};
+use crate::deserialize::read_len;
+use crate::serialize::write_len;
+
+/// Writes a `u32` length prefix, followed by each key and its value, in insertion order. This is the same format as for `&HashMap`.
+impl<E: Endianness, W: Write, K, V, S> Serialize<E, W> for &IndexMap<K, V, S> where for<'a> &'a K: Serialize<E, W>, for<'a> &'a V: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		for (key, value) in self {
+			E::serialize(key, writer)?;
+			E::serialize(value, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Reads the entries in the order they were written. If a key occurs more than once, it keeps the position of its first occurrence and the value of its last.
+impl<E: Endianness, R: Read, K: Deserialize<E, R>+Eq+Hash, V: Deserialize<E, R>, S: BuildHasher+Default> Deserialize<E, R> for IndexMap<K, V, S> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let mut map = IndexMap::with_capacity_and_hasher(len.min(MAP_PREALLOC_CAP), S::default());
+		for _ in 0..len {
+			let key = E::deserialize(reader)?;
+			let value = E::deserialize(reader)?;
+			map.insert(key, value);
+		}
+		Ok(map)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the elements in insertion order.
+impl<E: Endianness, W: Write, T, S> Serialize<E, W> for &IndexSet<T, S> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		for elem in self {
+			E::serialize(elem, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Reads the elements in the order they were written. Duplicates are only kept once, at the position of their first occurrence.
+impl<E: Endianness, R: Read, T: Deserialize<E, R>+Eq+Hash, S: BuildHasher+Default> Deserialize<E, R> for IndexSet<T, S> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let mut set = IndexSet::with_capacity_and_hasher(len.min(MAP_PREALLOC_CAP), S::default());
+		for _ in 0..len {
+			set.insert(E::deserialize(reader)?);
+		}
+		Ok(set)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use indexmap::{IndexMap, IndexSet};
+
+	#[test]
+	fn map_order() {
+		use crate::{LERead, LEWrite};
+		let mut map = IndexMap::new();
+		map.insert(3u8, 0xbaadu16);
+		map.insert(1, 0xf00d);
+		map.insert(2, 0xbeef);
+		let mut writer = vec![];
+		writer.ewrite(&map).unwrap();
+		assert_eq!(writer, b"\x03\x00\x00\x00\x03\xad\xba\x01\x0d\xf0\x02\xef\xbe");
+		let mut reader = &writer[..];
+		let val: IndexMap<u8, u16> = reader.eread().unwrap();
+		assert!(val.iter().eq(map.iter()));
+	}
+
+	#[test]
+	fn set_order() {
+		use crate::{BERead, BEWrite};
+		let set: IndexSet<String> = ["c", "a", "b"].iter().map(|s| s.to_string()).collect();
+		let mut writer = vec![];
+		writer.ewrite(&set).unwrap();
+		let mut reader = &writer[..];
+		let val: IndexSet<String> = reader.eread().unwrap();
+		assert!(val.iter().eq(["c", "a", "b"].iter()));
+	}
+}
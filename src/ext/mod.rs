@@ -0,0 +1,22 @@
+//! (De-)serializations for types from other crates, each behind a feature of the same name.
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "bitflags")]
+pub(crate) mod bitflags;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "either")]
+mod either;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "tokio")]
+pub(crate) mod tokio;
@@ -0,0 +1,67 @@
+use std::io::Result as Res;
+
+use ordered_float::OrderedFloat;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+macro_rules! impl_ordered_float {
+	($t:ident) => {
+		/// Writes the inner float. The bit pattern is kept as is, so NaNs with different payloads stay distinct, even though `OrderedFloat` considers them equal.
+		impl<E: Endianness, W> Serialize<E, W> for OrderedFloat<$t> where $t: Serialize<E, W> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				E::serialize(self.0, writer)
+			}
+		}
+
+		/// Reads the inner float.
+		impl<E: Endianness, R> Deserialize<E, R> for OrderedFloat<$t> where $t: Deserialize<E, R> {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				Ok(OrderedFloat(E::deserialize(reader)?))
+			}
+		}
+	}
+}
+
+impl_ordered_float!(f32);
+impl_ordered_float!(f64);
+
+#[cfg(test)]
+mod tests {
+	use ordered_float::OrderedFloat;
+
+	#[test]
+	fn f32() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(OrderedFloat(-1.5f32)).unwrap();
+		assert_eq!(writer, b"\xbf\xc0\x00\x00");
+		let mut reader = &writer[..];
+		let val: OrderedFloat<f32> = reader.eread().unwrap();
+		assert_eq!(val, OrderedFloat(-1.5));
+	}
+
+	#[test]
+	fn f64() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(OrderedFloat(0.1f64)).unwrap();
+		assert_eq!(writer, b"\x9a\x99\x99\x99\x99\x99\xb9\x3f");
+		let mut reader = &writer[..];
+		let val: OrderedFloat<f64> = reader.eread().unwrap();
+		assert_eq!(val, OrderedFloat(0.1));
+	}
+
+	#[test]
+	fn nan() {
+		use crate::{LERead, LEWrite};
+		let nan = f64::from_bits(0x7ff8_0000_baad_f00d);
+		let mut writer = vec![];
+		writer.ewrite(OrderedFloat(nan)).unwrap();
+		assert_eq!(writer, b"\x0d\xf0\xad\xba\x00\x00\xf8\x7f");
+		let mut reader = &writer[..];
+		let val: OrderedFloat<f64> = reader.eread().unwrap();
+		assert_eq!(val.0.to_bits(), nan.to_bits());
+		assert_eq!(val, OrderedFloat(f64::NAN));
+		assert!(val > OrderedFloat(f64::INFINITY));
+	}
+}
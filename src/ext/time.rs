@@ -0,0 +1,69 @@
+use std::io::Read;
+use std::io::Result as Res;
+use std::io::Write;
+
+use time::OffsetDateTime;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/// Writes the time in UTC, as the `i64` seconds since the Unix epoch, followed by the `u32` nanoseconds within that second.
+impl<E: Endianness, W: Write> Serialize<E, W> for OffsetDateTime {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.unix_timestamp(), writer)?;
+		E::serialize(self.nanosecond(), writer)
+	}
+}
+
+/// Reads the format written by `OffsetDateTime`'s `Serialize`, with a UTC offset. Returns an `InvalidData` error if the time is out of `time`'s range or the nanoseconds are 1_000_000_000 or more.
+impl<E: Endianness, R: Read> Deserialize<E, R> for OffsetDateTime {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let secs: i64 = E::deserialize(reader)?;
+		let nanos: u32 = E::deserialize(reader)?;
+		OffsetDateTime::from_unix_timestamp(secs)
+			.and_then(|time| time.replace_nanosecond(nanos))
+			.map_err(|_| invalid_data(format!("timestamp of {}s and {}ns is out of range", secs, nanos)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use time::{OffsetDateTime, UtcOffset};
+
+	#[test]
+	fn round_trip() {
+		use crate::{BERead, BEWrite};
+		let time = OffsetDateTime::from_unix_timestamp_nanos(1_709_213_820_000_000_042).unwrap();
+		let mut writer = vec![];
+		writer.ewrite(time).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00\x65\xe0\x88\x7c\x00\x00\x00\x2a");
+		let mut reader = &writer[..];
+		let val: OffsetDateTime = reader.eread().unwrap();
+		assert_eq!(val, time);
+		assert_eq!(val.offset(), UtcOffset::UTC);
+	}
+
+	#[test]
+	fn offset_normalized_to_utc() {
+		use crate::{LERead, LEWrite};
+		let utc = OffsetDateTime::from_unix_timestamp(1_709_213_820).unwrap();
+		let time = utc.to_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+		let mut writer = vec![];
+		writer.ewrite(time).unwrap();
+		let mut reader = &writer[..];
+		let val: OffsetDateTime = reader.eread().unwrap();
+		assert_eq!(val, time);
+		assert_eq!(val.offset(), UtcOffset::UTC);
+		assert_eq!((val.hour(), time.hour()), (13, 14));
+	}
+
+	#[test]
+	fn invalid_nanos() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x94\x35\x77"[..];
+		let err = reader.eread::<OffsetDateTime>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "timestamp of 0s and 2000000000ns is out of range");
+	}
+}
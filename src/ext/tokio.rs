@@ -0,0 +1,415 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::io;
+use std::io::Result as Res;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::adapters::crc32_update;
+use crate::deserialize::invalid_data;
+use crate::serialize::write_len;
+
+/**
+	Options for the message framing of `write_message` and `read_message`.
+
+	By default a message is a `u32` length prefix followed by the serialized value. With `checksum` enabled, a `u32` CRC-32 (IEEE) of the value's bytes is appended after them, not counted in the length prefix. With a `sync_word`, every message is preceded by it, which lets the reader find the next message after a corrupted one. Both sides need to use the same options.
+
+	## Examples
+
+	```
+	# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+	use endio::{LittleEndian, MessageFraming};
+
+	let framing = MessageFraming::new().checksum(true);
+	let mut writer = vec![];
+	framing.write::<LittleEndian, _, _>(&mut writer, 42u8).await.unwrap();
+	assert_eq!(writer, b"\x01\x00\x00\x00\x2a\x5b\x26\xb9\x09");
+
+	let mut reader = &writer[..];
+	let value: u8 = framing.read::<LittleEndian, _, _>(&mut reader).await.unwrap();
+	assert_eq!(value, 42);
+	# });
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MessageFraming {
+	checksum: bool,
+	sync_word: Option<u32>,
+	max_len: Option<usize>,
+}
+
+impl MessageFraming {
+	/// Creates the default framing, without a checksum.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Appends a CRC-32 of the value to every message, and verifies it when reading.
+	pub fn checksum(mut self, checksum: bool) -> Self {
+		self.checksum = checksum;
+		self
+	}
+
+	/**
+		Writes the `u32` sync word before every message, and makes reading skip ahead to it.
+
+		Use this together with `checksum` and `max_len` for noisy links: When a message's checksum doesn't match, or its length prefix exceeds `max_len`, reading scans forward to the next occurrence of the sync word, starting one byte after the sync word of the corrupted message, and tries again from there instead of returning an error. The bytes of the corrupted message that were already read are buffered and scanned as well, so a corrupted length prefix doesn't cause the messages it covers to be skipped. Choose a sync word that's unlikely to occur within messages, as a message could otherwise be read from a false match, although such a match then most likely fails its checksum too.
+
+		Use a `MessageReader` to read a stream of messages, as it keeps the buffered bytes between reads. `read` discards them when it returns, which loses the start of the following message if one was found within the bytes of a corrupted one.
+	*/
+	pub fn sync_word(mut self, sync_word: Option<u32>) -> Self {
+		self.sync_word = sync_word;
+		self
+	}
+
+	/**
+		Limits the length of a message read, not counting the length prefix and checksum.
+
+		Without a sync word, a longer length prefix is an `InvalidData` error. With one, it's treated as corrupted, and reading resynchronizes. Without a limit, a corrupted length prefix makes reading wait for up to 4 GiB of data before the corruption is noticed.
+	*/
+	pub fn max_len(mut self, max_len: usize) -> Self {
+		self.max_len = Some(max_len);
+		self
+	}
+
+	/**
+		Writes the value as a message.
+
+		The value is serialized into a buffer first, so that the length is known and the whole message is written at once.
+	*/
+	pub async fn write<E: Endianness, W: AsyncWrite+Unpin, S: Serialize<E, Vec<u8>>>(&self, writer: &mut W, value: S) -> Res<()> {
+		let mut body = vec![];
+		E::serialize(value, &mut body)?;
+		let mut message = Vec::with_capacity(12 + body.len());
+		if let Some(sync_word) = self.sync_word {
+			E::serialize(sync_word, &mut message)?;
+		}
+		write_len::<E, _>(&mut message, body.len())?;
+		message.extend_from_slice(&body);
+		if self.checksum {
+			E::serialize(!crc32_update(!0, &body), &mut message)?;
+		}
+		writer.write_all(&message).await
+	}
+
+	/**
+		Reads a message written with the same framing.
+
+		The entire message is read before it's deserialized, so partial reads from the underlying stream are handled. The buffer grows as the message's bytes arrive, so a corrupt length prefix doesn't cause a huge allocation up front. Returns an `InvalidData` error if the checksum doesn't match, in which case the message is discarded, or if the value doesn't take up the entire message. With a sync word, any bytes before it are skipped, and corrupted messages are skipped instead of returning an error, see `sync_word` for details.
+	*/
+	pub async fn read<E: Endianness, R: AsyncRead+Unpin, D: for<'a> Deserialize<E, &'a [u8]>>(&self, reader: &mut R) -> Res<D> {
+		let body = self.read_body::<E, _>(reader, &mut VecDeque::new()).await?;
+		deserialize_body(&body)
+	}
+
+	/// Reads the body of the next intact message, reading any bytes in `pending` before those of the reader.
+	async fn read_body<E: Endianness, R: AsyncRead+Unpin>(&self, reader: &mut R, pending: &mut VecDeque<u8>) -> Res<Vec<u8>> {
+		let sync = match self.sync_word {
+			None => None,
+			Some(sync_word) => {
+				let mut sync = [0; 4];
+				E::serialize(sync_word, &mut &mut sync[..])?;
+				Some(sync)
+			}
+		};
+		loop {
+			if let Some(sync) = &sync {
+				skip_to_sync(reader, pending, sync).await?;
+			}
+			// The bytes of the message after the sync word, in case it turns out to be corrupted.
+			let mut frame = vec![];
+			read_pending(reader, pending, &mut frame, 4).await?;
+			// Not checked against the thread's `ReadConfig`, which could belong to any task on this worker thread. `max_len` is the limit here.
+			let len: u32 = E::deserialize(&mut &frame[..])?;
+			let len = usize::try_from(len).map_err(|_| invalid_data(format!("message length {} doesn't fit in memory", len)))?;
+			let corrupted = match self.max_len {
+				Some(max_len) if len > max_len => {
+					if sync.is_none() {
+						return Err(invalid_data(format!("message length {} exceeds the maximum of {}", len, max_len)));
+					}
+					true
+				}
+				_ => {
+					read_pending(reader, pending, &mut frame, len).await?;
+					if self.checksum {
+						read_pending(reader, pending, &mut frame, 4).await?;
+						let (body, mut trailer) = frame[4..].split_at(len);
+						let expected: u32 = E::deserialize(&mut trailer)?;
+						let actual = !crc32_update(!0, body);
+						if actual != expected && sync.is_none() {
+							return Err(invalid_data(format!("message checksum is {:#010x}, expected {:#010x}", actual, expected)));
+						}
+						actual != expected
+					} else {
+						false
+					}
+				}
+			};
+			match sync {
+				Some(sync) if corrupted => {
+					// Scan again from one byte after the start of the corrupted message's sync word.
+					for &byte in frame.iter().rev().chain(sync[1..].iter().rev()) {
+						pending.push_front(byte);
+					}
+				}
+				_ => {
+					frame.drain(..4);
+					frame.truncate(len);
+					return Ok(frame);
+				}
+			}
+		}
+	}
+}
+
+/**
+	Reads a stream of messages framed with a `MessageFraming`.
+
+	This is the same as calling `MessageFraming::read` repeatedly, except that bytes read ahead while resynchronizing on a sync word are kept for the next read, so no message is lost. See `MessageFraming::sync_word` for details.
+
+	## Examples
+
+	```
+	# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+	use endio::{LittleEndian, MessageFraming, MessageReader};
+
+	let framing = MessageFraming::new().checksum(true).sync_word(Some(0xa55af00d)).max_len(1024);
+	let mut writer = vec![];
+	framing.write::<LittleEndian, _, _>(&mut writer, 42u8).await.unwrap();
+	framing.write::<LittleEndian, _, _>(&mut writer, 43u8).await.unwrap();
+
+	let mut reader = MessageReader::new(framing, &writer[..]);
+	assert_eq!(reader.read::<LittleEndian, u8>().await.unwrap(), 42);
+	assert_eq!(reader.read::<LittleEndian, u8>().await.unwrap(), 43);
+	# });
+	```
+*/
+#[derive(Debug)]
+pub struct MessageReader<R> {
+	reader: R,
+	framing: MessageFraming,
+	pending: VecDeque<u8>,
+}
+
+impl<R: AsyncRead+Unpin> MessageReader<R> {
+	pub fn new(framing: MessageFraming, reader: R) -> Self {
+		Self { reader, framing, pending: VecDeque::new() }
+	}
+
+	/// Returns the reader. Any bytes that were read ahead are discarded.
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	/// Reads the next message. See `MessageFraming::read` for details.
+	pub async fn read<E: Endianness, D: for<'a> Deserialize<E, &'a [u8]>>(&mut self) -> Res<D> {
+		let body = self.framing.read_body::<E, _>(&mut self.reader, &mut self.pending).await?;
+		deserialize_body(&body)
+	}
+}
+
+/// Deserializes the value from the body, returning an `InvalidData` error if it doesn't take up the entire body.
+fn deserialize_body<E: Endianness, D: for<'a> Deserialize<E, &'a [u8]>>(body: &[u8]) -> Res<D> {
+	let mut body = body;
+	let value = E::deserialize(&mut body)?;
+	if !body.is_empty() {
+		return Err(invalid_data(format!("message had {} trailing bytes", body.len())));
+	}
+	Ok(value)
+}
+
+/// Appends `len` bytes to the buffer, taking them from `pending` first and then from the reader. The buffer grows as bytes arrive.
+async fn read_pending<R: AsyncRead+Unpin>(reader: &mut R, pending: &mut VecDeque<u8>, buf: &mut Vec<u8>, len: usize) -> Res<()> {
+	let buffered = len.min(pending.len());
+	buf.extend(pending.drain(..buffered));
+	let remaining = len - buffered;
+	if reader.take(remaining as u64).read_to_end(buf).await? != remaining {
+		return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "message ended before its declared length"));
+	}
+	Ok(())
+}
+
+/// Reads until the last bytes read are the sync word.
+async fn skip_to_sync<R: AsyncRead+Unpin>(reader: &mut R, pending: &mut VecDeque<u8>, sync: &[u8; 4]) -> Res<()> {
+	let mut window = [0; 4];
+	for byte in &mut window {
+		*byte = next_byte(reader, pending).await?;
+	}
+	while window != *sync {
+		window.copy_within(1.., 0);
+		window[3] = next_byte(reader, pending).await?;
+	}
+	Ok(())
+}
+
+async fn next_byte<R: AsyncRead+Unpin>(reader: &mut R, pending: &mut VecDeque<u8>) -> Res<u8> {
+	match pending.pop_front() {
+		Some(byte) => Ok(byte),
+		None => reader.read_u8().await,
+	}
+}
+
+/**
+	Writes the value as a message: a `u32` length prefix, followed by the serialized value.
+
+	The value is serialized into a buffer first, so that the length is known and the whole message is written at once. See `MessageFraming` for other framing options.
+
+	## Examples
+
+	```
+	# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+	use endio::{read_message, write_message, LittleEndian};
+
+	let mut writer = vec![];
+	write_message::<LittleEndian, _, _>(&mut writer, 0xbaadu16).await.unwrap();
+	assert_eq!(writer, b"\x02\x00\x00\x00\xad\xba");
+
+	let mut reader = &writer[..];
+	let value: u16 = read_message::<LittleEndian, _, _>(&mut reader).await.unwrap();
+	assert_eq!(value, 0xbaad);
+	# });
+	```
+*/
+pub async fn write_message<E: Endianness, W: AsyncWrite+Unpin, S: Serialize<E, Vec<u8>>>(writer: &mut W, value: S) -> Res<()> {
+	MessageFraming::new().write(writer, value).await
+}
+
+/**
+	Reads a message written by `write_message`.
+
+	The entire message is read before it's deserialized, so partial reads from the underlying stream are handled. Returns an `InvalidData` error if the value doesn't take up the entire message.
+*/
+pub async fn read_message<E: Endianness, R: AsyncRead+Unpin, D: for<'a> Deserialize<E, &'a [u8]>>(reader: &mut R) -> Res<D> {
+	MessageFraming::new().read(reader).await
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use tokio::io::AsyncWriteExt;
+
+	use crate::{BigEndian, LittleEndian};
+	use super::{read_message, write_message, MessageFraming, MessageReader};
+
+	#[tokio::test]
+	async fn duplex() {
+		let (mut client, mut server) = tokio::io::duplex(4);
+		let send = async {
+			write_message::<BigEndian, _, _>(&mut client, "endio").await.unwrap();
+		};
+		let receive = async {
+			read_message::<BigEndian, _, String>(&mut server).await.unwrap()
+		};
+		let ((), received) = tokio::join!(send, receive);
+		assert_eq!(received, "endio");
+	}
+
+	#[tokio::test]
+	async fn trailing_bytes() {
+		let mut reader = &b"\x03\x00\x00\x00\xad\xba\x2a"[..];
+		let err = read_message::<LittleEndian, _, u16>(&mut reader).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[tokio::test]
+	async fn truncated() {
+		let (mut client, mut server) = tokio::io::duplex(16);
+		client.write_all(b"\x04\x00\x00\x00\xad\xba").await.unwrap();
+		drop(client);
+		let err = read_message::<LittleEndian, _, u32>(&mut server).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[tokio::test]
+	async fn huge_len() {
+		let mut reader = &b"\xff\xff\xff\xff\x2a"[..];
+		let err = read_message::<LittleEndian, _, u8>(&mut reader).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[tokio::test]
+	async fn checksum_corrupted() {
+		let framing = MessageFraming::new().checksum(true);
+		let mut writer = vec![];
+		framing.write::<BigEndian, _, _>(&mut writer, "endio").await.unwrap();
+		framing.write::<BigEndian, _, _>(&mut writer, "next").await.unwrap();
+		let mut reader = &writer[..];
+		assert_eq!(framing.read::<BigEndian, _, String>(&mut reader).await.unwrap(), "endio");
+
+		writer[10] ^= 0x20;
+		let mut reader = &writer[..];
+		let err = framing.read::<BigEndian, _, String>(&mut reader).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(framing.read::<BigEndian, _, String>(&mut reader).await.unwrap(), "next");
+	}
+
+	#[tokio::test]
+	async fn sync_word_resync() {
+		let framing = MessageFraming::new().checksum(true).sync_word(Some(0xa55a_f00d)).max_len(64);
+		let mut writer = vec![];
+		framing.write::<LittleEndian, _, _>(&mut writer, "endio").await.unwrap();
+		let corrupted = writer.len();
+		framing.write::<LittleEndian, _, _>(&mut writer, "lost").await.unwrap();
+		writer.extend_from_slice(b"\x00\x0d\xf0");
+		framing.write::<LittleEndian, _, _>(&mut writer, "next").await.unwrap();
+		assert_eq!(&writer[..8], b"\x0d\xf0\x5a\xa5\x09\x00\x00\x00");
+
+		writer[corrupted + 12] ^= 0x20;
+		let mut reader = &writer[..];
+		assert_eq!(framing.read::<LittleEndian, _, String>(&mut reader).await.unwrap(), "endio");
+		assert_eq!(framing.read::<LittleEndian, _, String>(&mut reader).await.unwrap(), "next");
+		assert!(reader.is_empty());
+		let err = framing.read::<LittleEndian, _, String>(&mut reader).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[tokio::test]
+	async fn sync_word_oversized_len() {
+		let framing = MessageFraming::new().checksum(true).sync_word(Some(0xa55a_f00d)).max_len(64);
+		let mut writer = vec![];
+		framing.write::<LittleEndian, _, _>(&mut writer, "lost").await.unwrap();
+		framing.write::<LittleEndian, _, _>(&mut writer, "next").await.unwrap();
+
+		writer[7] = 0x80;
+		let mut reader = &writer[..];
+		assert_eq!(framing.read::<LittleEndian, _, String>(&mut reader).await.unwrap(), "next");
+		assert!(reader.is_empty());
+	}
+
+	#[tokio::test]
+	async fn sync_word_len_covers_next() {
+		let framing = MessageFraming::new().checksum(true).sync_word(Some(0xa55a_f00d)).max_len(64);
+		let mut writer = vec![];
+		framing.write::<LittleEndian, _, _>(&mut writer, "lost").await.unwrap();
+		framing.write::<LittleEndian, _, _>(&mut writer, "next").await.unwrap();
+		framing.write::<LittleEndian, _, _>(&mut writer, "last").await.unwrap();
+
+		// Claims to be long enough to cover all of "next" and the start of "last".
+		writer[4] = 40;
+		let mut reader = MessageReader::new(framing, &writer[..]);
+		assert_eq!(reader.read::<LittleEndian, String>().await.unwrap(), "next");
+		assert_eq!(reader.read::<LittleEndian, String>().await.unwrap(), "last");
+		let err = reader.read::<LittleEndian, String>().await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[tokio::test]
+	async fn max_len_without_sync_word() {
+		let framing = MessageFraming::new().max_len(4);
+		let mut reader = &b"\x05\x00\x00\x00endio"[..];
+		let err = framing.read::<LittleEndian, _, u8>(&mut reader).await.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "message length 5 exceeds the maximum of 4");
+	}
+
+	#[test]
+	fn ignores_read_config() {
+		use crate::ReadConfig;
+		let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+		let mut reader = &b"\x01\x00\x00\x00\x2a"[..];
+		let val = ReadConfig::new().max_len(0).scope(|| runtime.block_on(read_message::<LittleEndian, _, u8>(&mut reader))).unwrap();
+		assert_eq!(val, 42);
+	}
+}
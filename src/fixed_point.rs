@@ -0,0 +1,204 @@
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/**
+	A fixed-point decimal number, stored as an `i64` with `SCALE` implied decimal places.
+
+	Only the raw integer is read/written, in the reader's/writer's endianness. The scale isn't part of the data, it's up to the format to agree on it. `SCALE` can be at most 18, as the factor `10^SCALE` otherwise overflows an `i64`.
+
+	## Examples
+
+	```
+	use endio::{FixedPoint, LEWrite};
+
+	let price = FixedPoint::<2>::from_f64(12.34);
+	assert_eq!(price.raw(), 1234);
+
+	let mut writer = vec![];
+	writer.ewrite(price).unwrap();
+	assert_eq!(writer, b"\xd2\x04\x00\x00\x00\x00\x00\x00");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedPoint<const SCALE: u32>(pub i64);
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+	const FACTOR: i64 = 10i64.pow(SCALE);
+
+	/// Creates the number from its raw integer representation.
+	pub const fn from_raw(raw: i64) -> Self {
+		Self(raw)
+	}
+
+	/// The raw integer representation, the value multiplied by `10^SCALE`.
+	pub const fn raw(self) -> i64 {
+		self.0
+	}
+
+	/**
+		Converts from a float, rounding to the nearest representable value.
+
+		Halfway cases are rounded away from zero. Values outside the representable range saturate to `i64::MIN`/`i64::MAX`, and NaN converts to zero.
+	*/
+	pub fn from_f64(value: f64) -> Self {
+		Self((value * Self::FACTOR as f64).round() as i64)
+	}
+
+	/// Converts to the nearest float. Raw values with more than 53 significant bits lose precision.
+	pub fn to_f64(self) -> f64 {
+		self.0 as f64 / Self::FACTOR as f64
+	}
+}
+
+impl<E: Endianness, W, const SCALE: u32> Serialize<E, W> for FixedPoint<SCALE> where i64: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0, writer)
+	}
+}
+
+impl<E: Endianness, R, const SCALE: u32> Deserialize<E, R> for FixedPoint<SCALE> where i64: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Self(E::deserialize(reader)?))
+	}
+}
+
+/**
+	A vector of `N` fixed-point decimal components, each stored as an `i32` with `SCALE` implied decimal places.
+
+	The raw integers are read/written one after another, in the reader's/writer's endianness, like `[i32; N]`. This is a compact way of storing e.g. normalized coordinates. As with `FixedPoint`, the scale isn't part of the data. `SCALE` can be at most 9, as the factor `10^SCALE` otherwise overflows an `i32`.
+
+	## Examples
+
+	```
+	use endio::{FixedVec, LEWrite};
+
+	let normal = FixedVec::<3, 2>::from_f64s([0.6, -0.8]);
+	assert_eq!(normal.raw(), [600, -800]);
+
+	let mut writer = vec![];
+	writer.ewrite(normal).unwrap();
+	assert_eq!(writer, b"\x58\x02\x00\x00\xe0\xfc\xff\xff");
+	```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedVec<const SCALE: u32, const N: usize>(pub [i32; N]);
+
+impl<const SCALE: u32, const N: usize> FixedVec<SCALE, N> {
+	const FACTOR: i32 = 10i32.pow(SCALE);
+
+	/// Creates the vector from the raw integer representations of its components.
+	pub const fn from_raw(raw: [i32; N]) -> Self {
+		Self(raw)
+	}
+
+	/// The raw integer representations of the components, the values multiplied by `10^SCALE`.
+	pub const fn raw(self) -> [i32; N] {
+		self.0
+	}
+
+	/// Converts from floats, rounding each component like `FixedPoint::from_f64`, saturating to `i32::MIN`/`i32::MAX`.
+	pub fn from_f64s(values: [f64; N]) -> Self {
+		Self(values.map(|value| (value * Self::FACTOR as f64).round() as i32))
+	}
+
+	/// Converts to the nearest floats.
+	pub fn to_f64s(self) -> [f64; N] {
+		self.0.map(|raw| raw as f64 / Self::FACTOR as f64)
+	}
+}
+
+impl<const SCALE: u32, const N: usize> Default for FixedVec<SCALE, N> {
+	fn default() -> Self {
+		Self([0; N])
+	}
+}
+
+impl<E: Endianness, W, const SCALE: u32, const N: usize> Serialize<E, W> for FixedVec<SCALE, N> where i32: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0, writer)
+	}
+}
+
+impl<E: Endianness, R, const SCALE: u32, const N: usize> Deserialize<E, R> for FixedVec<SCALE, N> where i32: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Self(E::deserialize(reader)?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{FixedPoint, FixedVec};
+
+	#[test]
+	fn scale_2() {
+		let val = FixedPoint::<2>::from_f64(12.34);
+		assert_eq!(val.raw(), 1234);
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x00\x00\x00\x04\xd2");
+			let mut reader = &writer[..];
+			let read: FixedPoint<2> = reader.eread().unwrap();
+			assert_eq!(read, val);
+			assert_eq!(read.to_f64(), 12.34);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\xd2\x04\x00\x00\x00\x00\x00\x00");
+			let mut reader = &writer[..];
+			let read: FixedPoint<2> = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+	}
+
+	#[test]
+	fn scale_4_negative() {
+		use crate::{LERead, LEWrite};
+		let val = FixedPoint::<4>::from_f64(-1.23456);
+		assert_eq!(val.raw(), -12346);
+		let mut writer = vec![];
+		writer.ewrite(val).unwrap();
+		assert_eq!(writer, b"\xc6\xcf\xff\xff\xff\xff\xff\xff");
+		let mut reader = &writer[..];
+		let read: FixedPoint<4> = reader.eread().unwrap();
+		assert_eq!(read, val);
+		assert_eq!(read.to_f64(), -1.2346);
+	}
+
+	#[test]
+	fn rounding() {
+		assert_eq!(FixedPoint::<2>::from_f64(0.125).raw(), 13);
+		assert_eq!(FixedPoint::<2>::from_f64(-0.125).raw(), -13);
+		assert_eq!(FixedPoint::<2>::from_f64(f64::NAN).raw(), 0);
+		assert_eq!(FixedPoint::<2>::from_f64(f64::INFINITY).raw(), i64::MAX);
+	}
+
+	#[test]
+	fn vec_scale_3() {
+		let val = FixedVec::<3, 3>::from_f64s([0.267, -0.535, 0.802]);
+		assert_eq!(val.raw(), [267, -535, 802]);
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x00\x00\x01\x0b\xff\xff\xfd\xe9\x00\x00\x03\x22");
+			let mut reader = &writer[..];
+			let read: FixedVec<3, 3> = reader.eread().unwrap();
+			assert_eq!(read, val);
+			assert_eq!(read.to_f64s(), [0.267, -0.535, 0.802]);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x0b\x01\x00\x00\xe9\xfd\xff\xff\x22\x03\x00\x00");
+			let mut reader = &writer[..];
+			let read: FixedVec<3, 3> = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+	}
+}
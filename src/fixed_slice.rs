@@ -0,0 +1,85 @@
+use std::io;
+use std::io::Write;
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/**
+	Exactly `N` elements without a length prefix, read into a `Vec`.
+
+	This is the same format as `[T; N]`, for when the count is implied by the schema but a `Vec` is more convenient than an array. A plain `&[T]` is written the same way, so this is mostly useful for reading. Writing a `FixedSlice` returns an `InvalidInput` error if it doesn't have exactly `N` elements.
+
+	## Examples
+
+	```
+	use endio::{FixedSlice, LERead};
+
+	let mut reader = &b"\x01\x00\x02\x00\x2a"[..];
+	let FixedSlice(vec) = reader.eread::<FixedSlice<2, u16>>().unwrap();
+	assert_eq!(vec, [1, 2]);
+	assert_eq!(reader, b"\x2a");
+	```
+*/
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedSlice<const N: usize, T>(pub Vec<T>);
+
+impl<E: Endianness, W: Write, const N: usize, T> Serialize<E, W> for &FixedSlice<N, T> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		if self.0.len() != N {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("slice has {} elements, expected {}", self.0.len(), N)));
+		}
+		for elem in &self.0 {
+			E::serialize(elem, writer)?;
+		}
+		Ok(())
+	}
+}
+
+impl<E: Endianness, R, const N: usize, T: Deserialize<E, R>> Deserialize<E, R> for FixedSlice<N, T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut vec = Vec::with_capacity(N);
+		for _ in 0..N {
+			vec.push(E::deserialize(reader)?);
+		}
+		Ok(FixedSlice(vec))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::FixedSlice;
+
+	#[test]
+	fn five_u32s() {
+		use crate::{BERead, BEWrite};
+		let slice = FixedSlice::<5, u32>(vec![1, 2, 3, 0xbaadf00d, 5]);
+		let mut writer = vec![];
+		writer.ewrite(&slice).unwrap();
+		assert_eq!(writer.len(), 20);
+		assert_eq!(&writer[12..16], b"\xba\xad\xf0\x0d");
+		let mut reader = &writer[..];
+		let val: FixedSlice<5, u32> = reader.eread().unwrap();
+		assert_eq!(val, slice);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn wrong_len() {
+		use std::io;
+		use crate::LEWrite;
+		let mut writer = vec![];
+		let err = writer.ewrite(&FixedSlice::<5, u32>(vec![1, 2])).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "slice has 2 elements, expected 5");
+		assert!(writer.is_empty());
+	}
+
+	#[test]
+	fn truncated() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x01\x00\x00\x00"[..];
+		let err = reader.eread::<FixedSlice<2, u32>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
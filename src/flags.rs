@@ -0,0 +1,142 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/**
+	`N` booleans packed into `ceil(N / 8)` bytes, for up to 128 flags.
+
+	Flag `i` is bit `i % 8` of byte `i / 8`, counting from the least significant bit, so the format doesn't depend on endianness. The unused bits of the last byte are written as zeros, and reading returns an `InvalidData` error if any of them are set.
+
+	## Examples
+
+	```
+	use endio::{BERead, BEWrite, Flags};
+
+	let mut flags = Flags::<12>::new();
+	flags.set(0, true);
+	flags.set(11, true);
+	let mut writer = vec![];
+	writer.ewrite(flags).unwrap();
+	assert_eq!(writer, b"\x01\x08");
+
+	let mut reader = &writer[..];
+	let read: Flags<12> = reader.eread().unwrap();
+	assert!(read.get(11));
+	assert!(!read.get(10));
+	```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flags<const N: usize>(u128);
+
+impl<const N: usize> Flags<N> {
+	const BYTES: usize = {
+		assert!(N <= 128, "Flags can hold at most 128 flags");
+		N.div_ceil(8)
+	};
+
+	/// Creates flags with all of them unset.
+	pub fn new() -> Self {
+		let _ = Self::BYTES;
+		Self(0)
+	}
+
+	/**
+		Whether the flag at the index is set.
+
+		# Panics
+
+		Panics if `index` is `N` or more.
+	*/
+	pub fn get(&self, index: usize) -> bool {
+		assert!(index < N, "flag index {} is out of range for {} flags", index, N);
+		self.0 & 1 << index != 0
+	}
+
+	/**
+		Sets or unsets the flag at the index.
+
+		# Panics
+
+		Panics if `index` is `N` or more.
+	*/
+	pub fn set(&mut self, index: usize, value: bool) {
+		assert!(index < N, "flag index {} is out of range for {} flags", index, N);
+		if value {
+			self.0 |= 1 << index;
+		} else {
+			self.0 &= !(1 << index);
+		}
+	}
+}
+
+impl<const N: usize> Default for Flags<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: Endianness, W: Write, const N: usize> Serialize<E, W> for Flags<N> {
+	const SIZE: Option<usize> = Some(Self::BYTES);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.write_all(&self.0.to_le_bytes()[..Self::BYTES])
+	}
+}
+
+impl<E: Endianness, R: Read, const N: usize> Deserialize<E, R> for Flags<N> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut buf = [0; 16];
+		reader.read_exact(&mut buf[..Self::BYTES])?;
+		let bits = u128::from_le_bytes(buf);
+		if N < 128 && bits >> N != 0 {
+			return Err(invalid_data(format!("Flags<{}> had unused bits set", N)));
+		}
+		Ok(Self(bits))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Flags;
+
+	#[test]
+	fn forty() {
+		use crate::{LERead, LEWrite};
+		let mut flags = Flags::<40>::new();
+		for i in (0..40).step_by(3) {
+			flags.set(i, true);
+		}
+		flags.set(39, true);
+		flags.set(0, false);
+		let mut writer = vec![];
+		writer.ewrite(flags).unwrap();
+		assert_eq!(writer, b"\x48\x92\x24\x49\x92");
+		let mut reader = &writer[..];
+		let val: Flags<40> = reader.eread().unwrap();
+		assert_eq!(val, flags);
+		assert!(!val.get(0));
+		assert!(val.get(3));
+		assert!(val.get(39));
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn stray_bits() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\xff\x10"[..];
+		let err = reader.eread::<Flags<12>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "Flags<12> had unused bits set");
+		let mut reader = &b"\xff\x0f"[..];
+		assert!(reader.eread::<Flags<12>>().is_ok());
+	}
+
+	#[test]
+	#[should_panic(expected = "flag index 40 is out of range for 40 flags")]
+	fn out_of_range() {
+		Flags::<40>::new().get(40);
+	}
+}
@@ -0,0 +1,99 @@
+use std::io::Result as Res;
+
+use crate::{BigEndian, Deserialize, Endianness, LittleEndian, Serialize};
+
+/**
+	Reads/writes the wrapped value in **big** endian, regardless of the reader's/writer's endianness.
+
+	This is the per-value counterpart to `read_be`/`write_be`, for use where those can't be called directly, e.g. inside tuples or collections. Wrapping an array, as in `Be<[u32; 3]>`, forces the endianness of every element.
+
+	## Examples
+
+	```
+	use endio::{Be, Le, LEWrite};
+
+	let mut writer = vec![];
+	writer.ewrite((Be(0xbaadu16), Le(0xbaadu16), 0xbaadu16)).unwrap();
+	assert_eq!(writer, b"\xba\xad\xad\xba\xad\xba");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Be<T>(pub T);
+
+/// Reads/writes the wrapped value in **little** endian, regardless of the reader's/writer's endianness. See `Be` for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Le<T>(pub T);
+
+impl<E: Endianness, W, T: Serialize<BigEndian, W>> Serialize<E, W> for Be<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		BigEndian::serialize(self.0, writer)
+	}
+}
+
+impl<E: Endianness, R, T: Deserialize<BigEndian, R>> Deserialize<E, R> for Be<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Be(BigEndian::deserialize(reader)?))
+	}
+}
+
+impl<E: Endianness, W, T: Serialize<LittleEndian, W>> Serialize<E, W> for Le<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		LittleEndian::serialize(self.0, writer)
+	}
+}
+
+impl<E: Endianness, R, T: Deserialize<LittleEndian, R>> Deserialize<E, R> for Le<T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(Le(LittleEndian::deserialize(reader)?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Be, Le};
+
+	#[test]
+	fn mixed_tuple() {
+		let data = b"\x0d\xf0\xad\xba\xba\xad";
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite((Le(0xbaadf00du32), Be(0xbaadu16))).unwrap();
+			assert_eq!(writer, data);
+			let mut reader = &writer[..];
+			let val: (Le<u32>, Be<u16>) = reader.eread().unwrap();
+			assert_eq!(val, (Le(0xbaadf00d), Be(0xbaad)));
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite((Le(0xbaadf00du32), Be(0xbaadu16))).unwrap();
+			assert_eq!(writer, data);
+			let mut reader = &writer[..];
+			let val: (Le<u32>, Be<u16>) = reader.eread().unwrap();
+			assert_eq!(val, (Le(0xbaadf00d), Be(0xbaad)));
+		}
+	}
+
+	#[test]
+	fn forced_in_ambient() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite((0xbaadu16, Le(0xbaadu16))).unwrap();
+		assert_eq!(writer, b"\xba\xad\xad\xba");
+		let mut reader = &writer[..];
+		let val: (u16, Le<u16>) = reader.eread().unwrap();
+		assert_eq!(val, (0xbaad, Le(0xbaad)));
+	}
+
+	#[test]
+	fn array_in_le() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite((Be([1u32, 0xbaadf00d, 3]), 0xbaadu16)).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x01\xba\xad\xf0\x0d\x00\x00\x00\x03\xad\xba");
+		let mut reader = &writer[..];
+		let val: (Be<[u32; 3]>, u16) = reader.eread().unwrap();
+		assert_eq!(val, (Be([1, 0xbaadf00d, 3]), 0xbaad));
+	}
+}
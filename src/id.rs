@@ -0,0 +1,142 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/// Parses 32 hex digits, ignoring any hyphens.
+fn parse_hex(s: &str) -> Option<u128> {
+	let digits: String = s.chars().filter(|&c| c != '-').collect();
+	if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	u128::from_str_radix(&digits, 16).ok()
+}
+
+macro_rules! impl_id {
+	($name:ident) => {
+		impl $name {
+			/// Parses the ID from 32 hex digits, ignoring hyphens, such as `"0123abcd-..."`. Returns `None` if the string isn't exactly 32 hex digits.
+			pub fn from_hex(s: &str) -> Option<Self> {
+				parse_hex(s).map(Self)
+			}
+		}
+
+		/// Always writes the ID in big endian, regardless of the writer's endianness.
+		impl<E: Endianness, W: Write> Serialize<E, W> for $name {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				writer.write_all(&self.0.to_be_bytes())
+			}
+		}
+
+		/// Always reads the ID in big endian, regardless of the reader's endianness.
+		impl<E: Endianness, R: Read> Deserialize<E, R> for $name {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let mut bytes = [0; 16];
+				reader.read_exact(&mut bytes)?;
+				Ok(Self(u128::from_be_bytes(bytes)))
+			}
+		}
+	}
+}
+
+/**
+	A 128-bit GUID/UUID, in the canonical byte order of RFC 4122: big endian, as the digits of its string form are read.
+
+	Note that this does not match the mixed-endian layout of Microsoft's `GUID` struct.
+
+	## Examples
+
+	```
+	use endio::{Guid, LEWrite};
+
+	let guid = Guid::from_hex("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+	let mut writer = vec![];
+	writer.ewrite(guid).unwrap();
+	assert_eq!(writer, b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99\xaa\xbb\xcc\xdd\xee\xff");
+	assert_eq!(guid.to_string(), "00112233-4455-6677-8899-aabbccddeeff");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(pub u128);
+
+/**
+	A 128-bit ULID, always in big endian, so that the byte order sorts like the IDs themselves.
+
+	ULIDs are usually displayed in Crockford's base32, this only provides hex conversions.
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid(pub u128);
+
+impl_id!(Guid);
+impl_id!(Ulid);
+
+/// Formats the GUID in its hyphenated lowercase hex form.
+impl fmt::Display for Guid {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let hex = format!("{:032x}", self.0);
+		write!(f, "{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+	}
+}
+
+/// Formats the ULID as 32 lowercase hex digits.
+impl fmt::Display for Ulid {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:032x}", self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Guid, Ulid};
+
+	const GUID: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+	const GUID_BYTES: &[u8] = b"\x6b\xa7\xb8\x10\x9d\xad\x11\xd1\x80\xb4\x00\xc0\x4f\xd4\x30\xc8";
+
+	#[test]
+	fn guid_big_endian() {
+		let guid = Guid::from_hex(GUID).unwrap();
+		assert_eq!(guid.0, 0x6ba7b8109dad11d180b400c04fd430c8);
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(guid).unwrap();
+			assert_eq!(writer, GUID_BYTES);
+			let mut reader = GUID_BYTES;
+			let val: Guid = reader.eread().unwrap();
+			assert_eq!(val, guid);
+		}
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(guid).unwrap();
+			assert_eq!(writer, GUID_BYTES);
+		}
+	}
+
+	#[test]
+	fn guid_display() {
+		assert_eq!(Guid::from_hex(GUID).unwrap().to_string(), GUID);
+		assert_eq!(Guid(42).to_string(), "00000000-0000-0000-0000-00000000002a");
+	}
+
+	#[test]
+	fn ulid() {
+		use crate::{LERead, LEWrite};
+		let ulid = Ulid::from_hex("01890a5d8a2b4c3d9e0f112233445566").unwrap();
+		let mut writer = vec![];
+		writer.ewrite(ulid).unwrap();
+		assert_eq!(writer, b"\x01\x89\x0a\x5d\x8a\x2b\x4c\x3d\x9e\x0f\x11\x22\x33\x44\x55\x66");
+		let mut reader = &writer[..];
+		let val: Ulid = reader.eread().unwrap();
+		assert_eq!(val, ulid);
+		assert_eq!(val.to_string(), "01890a5d8a2b4c3d9e0f112233445566");
+	}
+
+	#[test]
+	fn invalid_hex() {
+		assert_eq!(Guid::from_hex("6ba7b810-9dad-11d1-80b4-00c04fd430c"), None);
+		assert_eq!(Guid::from_hex("6ba7b810-9dad-11d1-80b4-00c04fd430cg"), None);
+		assert_eq!(Ulid::from_hex("+1890a5d8a2b4c3d9e0f112233445566"), None);
+	}
+}
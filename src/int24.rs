@@ -0,0 +1,120 @@
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+macro_rules! impl_int24 {
+	($name:ident, $t:ident, $min:expr, $max:expr, $doc:literal) => {
+		#[doc = $doc]
+		///
+		/// Writing returns an `InvalidInput` error if the value doesn't fit in 24 bits.
+		#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		pub struct $name(pub $t);
+
+		impl $name {
+			pub const MIN: $t = $min;
+			pub const MAX: $t = $max;
+
+			fn check(self) -> Res<$t> {
+				if self.0 < Self::MIN || self.0 > Self::MAX {
+					return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} is out of range for a 24-bit integer", self.0)));
+				}
+				Ok(self.0)
+			}
+		}
+
+		impl<E: Endianness, W: Write> Serialize<E, W> for $name {
+			fn serialize_be(self, writer: &mut W) -> Res<()> {
+				writer.write_all(&self.check()?.to_be_bytes()[1..])
+			}
+
+			fn serialize_le(self, writer: &mut W) -> Res<()> {
+				writer.write_all(&self.check()?.to_le_bytes()[..3])
+			}
+		}
+
+		impl<E: Endianness, R: Read> Deserialize<E, R> for $name {
+			fn deserialize_be(reader: &mut R) -> Res<Self> {
+				let mut buf = [0; 4];
+				reader.read_exact(&mut buf[..3])?;
+				// shifting the bytes to the top and back sign-extends signed values
+				Ok(Self($t::from_be_bytes(buf) >> 8))
+			}
+
+			fn deserialize_le(reader: &mut R) -> Res<Self> {
+				let mut buf = [0; 4];
+				reader.read_exact(&mut buf[1..])?;
+				Ok(Self($t::from_le_bytes(buf) >> 8))
+			}
+		}
+	}
+}
+
+impl_int24!(U24, u32, 0, (1 << 24) - 1, "An unsigned 24-bit integer, read/written as 3 bytes.");
+impl_int24!(I24, i32, -(1 << 23), (1 << 23) - 1, "A signed 24-bit integer, read/written as 3 bytes in two's complement.");
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::{I24, U24};
+
+	#[test]
+	fn unsigned() {
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(U24(0xbaadf0)).unwrap();
+			assert_eq!(writer, b"\xba\xad\xf0");
+			let mut reader = &writer[..];
+			let val: U24 = reader.eread().unwrap();
+			assert_eq!(val, U24(0xbaadf0));
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(U24(0xbaadf0)).unwrap();
+			assert_eq!(writer, b"\xf0\xad\xba");
+			let mut reader = &writer[..];
+			let val: U24 = reader.eread().unwrap();
+			assert_eq!(val, U24(0xbaadf0));
+		}
+	}
+
+	#[test]
+	fn signed() {
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(I24(-2)).unwrap();
+			writer.ewrite(I24(0x123456)).unwrap();
+			assert_eq!(writer, b"\xff\xff\xfe\x12\x34\x56");
+			let mut reader = &writer[..];
+			assert_eq!(reader.eread::<I24>().unwrap(), I24(-2));
+			assert_eq!(reader.eread::<I24>().unwrap(), I24(0x123456));
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(I24(I24::MIN)).unwrap();
+			writer.ewrite(I24(I24::MAX)).unwrap();
+			assert_eq!(writer, b"\x00\x00\x80\xff\xff\x7f");
+			let mut reader = &writer[..];
+			assert_eq!(reader.eread::<I24>().unwrap(), I24(-0x800000));
+			assert_eq!(reader.eread::<I24>().unwrap(), I24(0x7fffff));
+		}
+	}
+
+	#[test]
+	fn out_of_range() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		let err = writer.ewrite(U24(1 << 24)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "16777216 is out of range for a 24-bit integer");
+		let err = writer.ewrite(I24(-0x800001)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(writer.is_empty());
+	}
+}
@@ -0,0 +1,152 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+use std::net::{IpAddr, Ipv6Addr};
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/**
+	An IP address always written as 16 bytes, with IPv4 addresses in their IPv4-mapped IPv6 form (`::ffff:a.b.c.d`).
+
+	The octets are written in network order regardless of endianness. When reading, IPv4-mapped addresses are returned as `IpAddr::V4`, so an IPv6 address that was already in mapped form also reads back as IPv4.
+
+	## Examples
+
+	```
+	use std::net::{IpAddr, Ipv4Addr};
+	use endio::{Ip16, LERead, LEWrite};
+
+	let addr = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+	let mut writer = vec![];
+	writer.ewrite(Ip16(addr)).unwrap();
+	assert_eq!(writer, b"\0\0\0\0\0\0\0\0\0\0\xff\xff\xc0\xa8\x00\x01");
+
+	let mut reader = &writer[..];
+	let Ip16(read) = reader.eread().unwrap();
+	assert_eq!(read, addr);
+	```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ip16(pub IpAddr);
+
+impl<E: Endianness, W: Write> Serialize<E, W> for Ip16 {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let addr = match self.0 {
+			IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+			IpAddr::V6(addr) => addr,
+		};
+		writer.write_all(&addr.octets())
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for Ip16 {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut octets = [0; 16];
+		reader.read_exact(&mut octets)?;
+		let addr = Ipv6Addr::from(octets);
+		Ok(Ip16(match addr.to_ipv4_mapped() {
+			Some(addr) => IpAddr::V4(addr),
+			None => IpAddr::V6(addr),
+		}))
+	}
+}
+
+/**
+	An IPv6 address written as its eight 16-bit segments, each in the writer's endianness.
+
+	This differs from the octets of `Ip16` for formats that store the address as an array of `u16`s, e.g. little endian ones. In big endian the bytes are the same as in network order.
+
+	## Examples
+
+	```
+	use std::net::Ipv6Addr;
+	use endio::{Ipv6Segments, LEWrite};
+
+	let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+	let mut writer = vec![];
+	writer.ewrite(Ipv6Segments(addr)).unwrap();
+	assert_eq!(writer, b"\x01\x20\xb8\x0d\0\0\0\0\0\0\0\0\0\0\x01\x00");
+	```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv6Segments(pub Ipv6Addr);
+
+impl<E: Endianness, W> Serialize<E, W> for Ipv6Segments where u16: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0.segments(), writer)
+	}
+}
+
+impl<E: Endianness, R> Deserialize<E, R> for Ipv6Segments where u16: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let segments: [u16; 8] = E::deserialize(reader)?;
+		Ok(Ipv6Segments(Ipv6Addr::from(segments)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+	use super::{Ip16, Ipv6Segments};
+
+	#[test]
+	fn v4() {
+		use crate::{BERead, BEWrite};
+		let addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 42));
+		let mut writer = vec![];
+		writer.ewrite(Ip16(addr)).unwrap();
+		assert_eq!(writer, b"\0\0\0\0\0\0\0\0\0\0\xff\xff\x0a\x00\x00\x2a");
+		let mut reader = &writer[..];
+		let val: Ip16 = reader.eread().unwrap();
+		assert_eq!(val.0, addr);
+	}
+
+	#[test]
+	fn v6() {
+		use crate::{LERead, LEWrite};
+		let addr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0xbaad, 0xf00d));
+		let mut writer = vec![];
+		writer.ewrite(Ip16(addr)).unwrap();
+		assert_eq!(writer, b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\xba\xad\xf0\x0d");
+		let mut reader = &writer[..];
+		let val: Ip16 = reader.eread().unwrap();
+		assert_eq!(val.0, addr);
+	}
+
+	#[test]
+	fn mapped_v6_collapses() {
+		use crate::{LERead, LEWrite};
+		let addr = Ipv4Addr::new(127, 0, 0, 1);
+		let mut writer = vec![];
+		writer.ewrite(Ip16(IpAddr::V6(addr.to_ipv6_mapped()))).unwrap();
+		let mut reader = &writer[..];
+		let val: Ip16 = reader.eread().unwrap();
+		assert_eq!(val.0, IpAddr::V4(addr));
+	}
+
+	#[test]
+	fn segments() {
+		let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0xbaad, 0xf00d);
+		let be = {
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(Ipv6Segments(addr)).unwrap();
+			assert_eq!(writer, b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\xba\xad\xf0\x0d");
+			let mut reader = &writer[..];
+			let val: Ipv6Segments = reader.eread().unwrap();
+			assert_eq!(val.0, addr);
+			writer
+		};
+		let le = {
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(Ipv6Segments(addr)).unwrap();
+			assert_eq!(writer, b"\x01\x20\xb8\x0d\0\0\0\0\0\0\0\0\xad\xba\x0d\xf0");
+			let mut reader = &writer[..];
+			let val: Ipv6Segments = reader.eread().unwrap();
+			assert_eq!(val.0, addr);
+			writer
+		};
+		assert_ne!(be, le);
+	}
+}
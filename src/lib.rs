@@ -20,6 +20,8 @@
 
 	This crate is entirely trait-based, which means it adds zero state at runtime. All the information needed is encoded at the type level. All functionality is delegated to (de-)serialization code.
 
+	The one exception is `ReadConfig`: Its limits are kept in thread-local state for the duration of `ReadConfig::scope`, so that they reach every nested read without changing the reader's type.
+
 	Compared to the alternative way of per-object endianness specification via a wrapper type, traits also have the advantage that they don't limit access to the specific type at all, whereas with wrappers you need to explicitly get a reference the underlying object to use it.
 
 	### Zero-cost abstractions
@@ -96,16 +98,108 @@
 	To conduct I/O you `use` the traits `BERead` & `BEWrite`, or `LERead` & `LEWrite`. Choose `BERead` & `BEWrite` for big endian I/O, and `LERead` & `LEWrite` for little endian I/O. This will give you the `read`/`write` methods on your structs. `read` returns values of your desired type, and `write` accepts values as a parameter. The deserialization to be used and the type to be returned are handled through type inference, so most of the time you won't even need to annotate the type explicitly.
 
 	You can read and write your own types by implementing `Serialize`/`Deserialize`. See their documentation for details.
+
+	## Optional features
+
+	(De-)serializations for types from other crates can be enabled through features of the same name:
+
+	- `arrayvec`: `ArrayString`, in the same format as strings.
+	- `bitflags`: Flag sets, through the `BitFlags` and `BitFlagsRetain` wrappers.
+	- `bytes`: `Bytes` and `BytesMut`, length-prefixed.
+	- `chrono`: `DateTime`, as seconds and nanoseconds since the Unix epoch in UTC.
+	- `either`: `Either`, with a `u8` tag of 0 for `Left` and 1 for `Right`.
+	- `glam`: Vectors and quaternions of `f32` and `f64`, as their components in field order.
+	- `indexmap`: `IndexMap` and `IndexSet`, length-prefixed, in insertion order.
+	- `ordered-float`: `OrderedFloat<f32>` and `OrderedFloat<f64>`, in the same format as the inner float.
+	- `time`: `OffsetDateTime`, as seconds and nanoseconds since the Unix epoch in UTC.
+	- `tokio`: `write_message`/`read_message` for length-delimited messages over `AsyncWrite`/`AsyncRead`, optionally checksummed and resynchronized on a sync word through `MessageFraming` and `MessageReader`.
+
+	The `default-big-endian` feature makes `DefaultEndian` an alias of `BigEndian` instead of `LittleEndian`.
 */
 
 mod endian;
 mod read;
 mod write;
 mod deserialize;
+mod borrowed;
+mod config;
 mod serialize;
+mod adapters;
+mod string_table;
+mod string_policy;
+mod fixed_point;
+mod int24;
+mod sign_magnitude;
+mod canonical;
+mod saturating;
+mod compact_len;
+mod duration;
+mod forced;
+mod niche;
+mod or_default;
+mod id;
+mod bit_vec;
+mod bits;
+mod bit_reversed;
+mod flags;
+mod delimited;
+mod varint;
+mod delta;
+mod tagged;
+mod fixed_slice;
+mod aligned;
+mod columnar;
+mod rle;
+mod typed;
+mod ip;
+mod nullable;
+mod self_inclusive;
+mod registry;
+mod value;
+pub mod bytes;
+mod ext;
+#[cfg(test)]
+mod round_trip;
 
 pub use self::endian::*;
 pub use self::read::*;
 pub use self::write::*;
 pub use self::deserialize::*;
+pub use self::borrowed::*;
+pub use self::config::*;
 pub use self::serialize::*;
+pub use self::adapters::*;
+pub use self::string_table::*;
+pub use self::string_policy::*;
+pub use self::fixed_point::*;
+pub use self::int24::*;
+pub use self::sign_magnitude::*;
+pub use self::canonical::*;
+pub use self::saturating::*;
+pub use self::compact_len::*;
+pub use self::duration::*;
+pub use self::forced::*;
+pub use self::niche::*;
+pub use self::or_default::*;
+pub use self::id::*;
+pub use self::bit_vec::*;
+pub use self::bits::*;
+pub use self::bit_reversed::*;
+pub use self::flags::*;
+pub use self::delimited::*;
+pub use self::delta::*;
+pub use self::tagged::*;
+pub use self::fixed_slice::*;
+pub use self::aligned::*;
+pub use self::columnar::*;
+pub use self::rle::*;
+pub use self::typed::*;
+pub use self::ip::*;
+pub use self::nullable::*;
+pub use self::self_inclusive::*;
+pub use self::registry::*;
+pub use self::value::*;
+#[cfg(feature = "bitflags")]
+pub use self::ext::bitflags::*;
+#[cfg(feature = "tokio")]
+pub use self::ext::tokio::*;
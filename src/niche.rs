@@ -0,0 +1,93 @@
+use std::io::Result as Res;
+use std::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroU8};
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/**
+	An `Option` that encodes `None` as a value the type itself can never have, instead of a separate presence flag.
+
+	Implemented for the `NonZero*` integers, where `None` is written as 0, so only the width of the integer is written either way.
+
+	## Examples
+
+	```
+	use std::num::NonZeroU32;
+	use endio::{LEWrite, NicheOption};
+
+	let mut writer = vec![];
+	writer.ewrite(NicheOption(NonZeroU32::new(42))).unwrap();
+	writer.ewrite(NicheOption::<NonZeroU32>(None)).unwrap();
+	assert_eq!(writer, b"\x2a\x00\x00\x00\x00\x00\x00\x00");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NicheOption<T>(pub Option<T>);
+
+macro_rules! impl_nonzero {
+	($t:ident, $int:ident) => {
+		impl<E: Endianness, W> Serialize<E, W> for NicheOption<$t> where $int: Serialize<E, W> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				E::serialize(self.0.map_or(0, $t::get), writer)
+			}
+		}
+
+		impl<E: Endianness, R> Deserialize<E, R> for NicheOption<$t> where $int: Deserialize<E, R> {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				Ok(NicheOption($t::new(E::deserialize(reader)?)))
+			}
+		}
+	}
+}
+
+impl_nonzero!(NonZeroU8, u8);
+impl_nonzero!(NonZeroU16, u16);
+impl_nonzero!(NonZeroU32, u32);
+impl_nonzero!(NonZeroU64, u64);
+impl_nonzero!(NonZeroU128, u128);
+impl_nonzero!(NonZeroI8, i8);
+impl_nonzero!(NonZeroI16, i16);
+impl_nonzero!(NonZeroI32, i32);
+impl_nonzero!(NonZeroI64, i64);
+impl_nonzero!(NonZeroI128, i128);
+
+#[cfg(test)]
+mod tests {
+	use std::num::{NonZeroI16, NonZeroU32};
+
+	use super::NicheOption;
+
+	#[test]
+	fn some() {
+		let val = NicheOption(NonZeroU32::new(0xbaadf00d));
+		{
+			use crate::{BERead, BEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\xba\xad\xf0\x0d");
+			let mut reader = &writer[..];
+			let read: NicheOption<NonZeroU32> = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+		{
+			use crate::{LERead, LEWrite};
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x0d\xf0\xad\xba");
+			let mut reader = &writer[..];
+			let read: NicheOption<NonZeroU32> = reader.eread().unwrap();
+			assert_eq!(read, val);
+		}
+	}
+
+	#[test]
+	fn none() {
+		use crate::{LERead, LEWrite};
+		let val = NicheOption::<NonZeroI16>(None);
+		let mut writer = vec![];
+		writer.ewrite(val).unwrap();
+		assert_eq!(writer, b"\x00\x00");
+		let mut reader = &writer[..];
+		let read: NicheOption<NonZeroI16> = reader.eread().unwrap();
+		assert_eq!(read, val);
+	}
+}
@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, PolicyString, Serialize, Strict, StringPolicy};
+use crate::config::check_len;
+use crate::deserialize::read_bytes;
+
+/**
+	Bytes or a string with a signed `i32` length prefix, where a negative length stands for `None`.
+
+	This is how formats like Thrift and Avro distinguish a null from an empty value: `None` is written as a length of -1, while `Some` of an empty value is written as a length of 0. Any negative length is read as `None`.
+
+	Any `T: AsRef<[u8]>` can be written. Reading is implemented for `String`, `PolicyString<P>`, `Vec<u8>` and `Box<[u8]>`.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, NullableLengthPrefixed};
+
+	let mut writer = vec![];
+	writer.ewrite(NullableLengthPrefixed(Some("endio"))).unwrap();
+	writer.ewrite(NullableLengthPrefixed::<&str>(None)).unwrap();
+	assert_eq!(writer, b"\x05\x00\x00\x00endio\xff\xff\xff\xff");
+
+	let mut reader = &writer[..];
+	let value: NullableLengthPrefixed<String> = reader.eread().unwrap();
+	assert_eq!(value.0.as_deref(), Some("endio"));
+	let value: NullableLengthPrefixed<String> = reader.eread().unwrap();
+	assert_eq!(value.0, None);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NullableLengthPrefixed<T>(pub Option<T>);
+
+impl<E: Endianness, W: Write, T: AsRef<[u8]>> Serialize<E, W> for NullableLengthPrefixed<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		match self.0 {
+			None => E::serialize(-1i32, writer),
+			Some(value) => {
+				let bytes = value.as_ref();
+				let len = i32::try_from(bytes.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length does not fit in i32 prefix"))?;
+				E::serialize(len, writer)?;
+				writer.write_all(bytes)
+			}
+		}
+	}
+}
+
+/// Reads the prefix, followed by that many bytes if it isn't negative.
+fn read_nullable<E: Endianness, R: Read>(reader: &mut R) -> Res<Option<Vec<u8>>> {
+	let len: i32 = E::deserialize(reader)?;
+	if len < 0 {
+		return Ok(None);
+	}
+	read_bytes(reader, check_len(len as usize)?).map(Some)
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for NullableLengthPrefixed<Vec<u8>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(NullableLengthPrefixed(read_nullable::<E, R>(reader)?))
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for NullableLengthPrefixed<Box<[u8]>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		Ok(NullableLengthPrefixed(read_nullable::<E, R>(reader)?.map(Vec::into_boxed_slice)))
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for NullableLengthPrefixed<String> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let NullableLengthPrefixed(string) = E::deserialize::<R, NullableLengthPrefixed<PolicyString<Strict>>>(reader)?;
+		Ok(NullableLengthPrefixed(string.map(PolicyString::into_inner)))
+	}
+}
+
+impl<E: Endianness, R: Read, P: StringPolicy> Deserialize<E, R> for NullableLengthPrefixed<PolicyString<P>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let string = read_nullable::<E, R>(reader)?.map(P::decode).transpose()?;
+		Ok(NullableLengthPrefixed(string.map(PolicyString::new)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::NullableLengthPrefixed;
+
+	#[test]
+	fn present() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(NullableLengthPrefixed(Some(&b"\xba\xad"[..]))).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x02\xba\xad");
+		let mut reader = &writer[..];
+		let val: NullableLengthPrefixed<Vec<u8>> = reader.eread().unwrap();
+		assert_eq!(val.0, Some(vec![0xba, 0xad]));
+	}
+
+	#[test]
+	fn empty() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(NullableLengthPrefixed(Some(""))).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+		let mut reader = &writer[..];
+		let val: NullableLengthPrefixed<String> = reader.eread().unwrap();
+		assert_eq!(val.0.as_deref(), Some(""));
+	}
+
+	#[test]
+	fn null() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(NullableLengthPrefixed::<Box<[u8]>>(None)).unwrap();
+		assert_eq!(writer, b"\xff\xff\xff\xff");
+		let mut reader = &writer[..];
+		let val: NullableLengthPrefixed<Box<[u8]>> = reader.eread().unwrap();
+		assert_eq!(val.0, None);
+		let mut reader = &b"\xfe\xff\xff\xff"[..];
+		let val: NullableLengthPrefixed<String> = reader.eread().unwrap();
+		assert_eq!(val.0, None);
+	}
+
+	#[test]
+	fn invalid_utf8() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x01\xff"[..];
+		let err = reader.eread::<NullableLengthPrefixed<String>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn lossy() {
+		use crate::{BERead, Lossy, PolicyString};
+		let mut reader = &b"\x00\x00\x00\x01\xff"[..];
+		let val: NullableLengthPrefixed<PolicyString<Lossy>> = reader.eread().unwrap();
+		assert_eq!(val.0.unwrap().0, "\u{fffd}");
+	}
+}
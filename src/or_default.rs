@@ -0,0 +1,91 @@
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+/**
+	An `Option` with a `u8` presence flag: 0 for `None`, or 1 followed by the value for `Some`.
+
+	Use `read_option_or` to read it with a default value for absent fields instead of `None`. This simplifies migrating old records, where a field newer versions always write may be absent. Presence flags other than 0 and 1 are an `InvalidData` error.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, OptionOrDefault};
+
+	let mut writer = vec![];
+	writer.ewrite(OptionOrDefault(Some(42u16))).unwrap();
+	writer.ewrite(OptionOrDefault::<u16>(None)).unwrap();
+	assert_eq!(writer, b"\x01\x2a\x00\x00");
+
+	let mut reader = &writer[..];
+	assert_eq!(reader.read_option_or(7u16).unwrap(), 42);
+	assert_eq!(reader.read_option_or(7u16).unwrap(), 7);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OptionOrDefault<T>(pub Option<T>);
+
+impl<E: Endianness, W, T: Serialize<E, W>> Serialize<E, W> for OptionOrDefault<T> where u8: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		match self.0 {
+			None => E::serialize(0u8, writer),
+			Some(value) => {
+				E::serialize(1u8, writer)?;
+				E::serialize(value, writer)
+			}
+		}
+	}
+}
+
+impl<E: Endianness, R, T: Deserialize<E, R>> Deserialize<E, R> for OptionOrDefault<T> where u8: Deserialize<E, R> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let flag: u8 = E::deserialize(reader)?;
+		match flag {
+			0 => Ok(OptionOrDefault(None)),
+			1 => Ok(OptionOrDefault(Some(E::deserialize(reader)?))),
+			_ => Err(invalid_data(format!("presence flag was {}, expected 0 or 1", flag))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::OptionOrDefault;
+
+	#[test]
+	fn present() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(OptionOrDefault(Some(0xbaadu16))).unwrap();
+		assert_eq!(writer, b"\x01\xba\xad");
+		let mut reader = &writer[..];
+		assert_eq!(reader.read_option_or(0xf00du16).unwrap(), 0xbaad);
+		let mut reader = &writer[..];
+		let val: OptionOrDefault<u16> = reader.eread().unwrap();
+		assert_eq!(val.0, Some(0xbaad));
+	}
+
+	#[test]
+	fn absent_with_default() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(OptionOrDefault::<&str>(None)).unwrap();
+		writer.ewrite(42u8).unwrap();
+		assert_eq!(writer, b"\x00\x2a");
+		let mut reader = &writer[..];
+		assert_eq!(reader.read_option_or(String::from("endio")).unwrap(), "endio");
+		let next: u8 = reader.eread().unwrap();
+		assert_eq!(next, 42);
+	}
+
+	#[test]
+	fn invalid_flag() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x02\x2a"[..];
+		let err = reader.read_option_or(0u8).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "presence flag was 2, expected 0 or 1");
+	}
+}
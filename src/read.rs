@@ -1,13 +1,70 @@
-use std::io::Read;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::io;
+use std::io::{Read, Take};
 use std::io::Result as Res;
 
-use crate::{BigEndian, Deserialize, Endianness, LittleEndian};
+use crate::{BigEndian, Deserialize, DeserializeBorrowed, Endianness, LittleEndian, OptionOrDefault, Positioned, PositionTrackingReader, StringPolicy};
+use crate::string_policy::read_string;
+use crate::deserialize::{invalid_data, read_map};
+
+fn read_header<E: Endianness, R>(reader: &mut R, magic: &[u8]) -> Res<u16> where u8: Deserialize<E, R>, u16: Deserialize<E, R> {
+	let mut found: Vec<u8> = Vec::with_capacity(magic.len());
+	for _ in magic {
+		found.push(E::deserialize(reader)?);
+	}
+	if found != magic {
+		return Err(invalid_data(format!("header had magic {:02x?}, expected {:02x?}", found, magic)));
+	}
+	E::deserialize(reader)
+}
+
+fn read_filling<E: Endianness, R: Read, D>(reader: &mut R, buf: &mut [D]) -> Res<usize> where D: for<'a> Deserialize<E, PositionTrackingReader<&'a mut R>> {
+	for (i, elem) in buf.iter_mut().enumerate() {
+		let mut tracker = PositionTrackingReader::new(&mut *reader);
+		match E::deserialize(&mut tracker) {
+			Ok(value) => *elem = value,
+			Err(e) if e.kind() == io::ErrorKind::UnexpectedEof && tracker.position() == 0 => return Ok(i),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(buf.len())
+}
+
+fn read_versioned<R: Read, T, F: FnOnce(&mut Take<&mut R>) -> Res<T>>(reader: &mut R, len: u64, body: F) -> Res<T> {
+	let mut record = reader.take(len);
+	let value = body(&mut record)?;
+	let remaining = record.limit();
+	if io::copy(&mut record, &mut io::sink())? != remaining {
+		return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "record ended before its declared length"));
+	}
+	Ok(value)
+}
+
+fn read_checked<E: Endianness, R: Read, D: Deserialize<E, PositionTrackingReader<R>>>(reader: R, size: u64) -> Res<D> {
+	let mut tracker = PositionTrackingReader::new(reader);
+	let value = E::deserialize(&mut tracker)?;
+	if tracker.position() != size {
+		return Err(invalid_data(format!("value was read from {} bytes, expected {}", tracker.position(), size)));
+	}
+	Ok(value)
+}
+
+fn check_range<D: PartialOrd+Display>(value: D, min: D, max: D) -> Res<D> {
+	if value < min || value > max {
+		return Err(invalid_data(format!("value {} out of range {}..={}", value, min, max)));
+	}
+	Ok(value)
+}
 
 /**
 	Only necessary for custom (de-)serializations.
 
 	Interface for reading data with a specified endianness. Use this interface to make deserializations automatically switch endianness without having to write the same code twice.
 
+	As with `EWrite`, trait objects are read from through `&mut dyn Read` or `Box<dyn Read>`.
+
 	In theory this would be the only write trait and BE-/LERead would be aliases to the BE/LE type parameter variants, but for some reason that doesn't import methods in `use` notation.
 
 	## Examples
@@ -35,6 +92,117 @@ pub trait ERead<E: Endianness>: Sized + Read {
 	fn read_be<D: Deserialize<BigEndian,    Self>>(&mut self) -> Res<D> { BigEndian::deserialize(self) }
 	/// Reads in forced little endian.
 	fn read_le<D: Deserialize<LittleEndian, Self>>(&mut self) -> Res<D> { LittleEndian::deserialize(self) }
+	/**
+		Reads in the reader's endianness, returning an `InvalidData` error if the value is outside of `min..=max`.
+
+		## Examples
+
+		```
+		use endio::LERead;
+
+		let mut reader = &b"\xe8\x03\xe9\x03"[..];
+		assert_eq!(reader.read_ranged(0u16, 1000).unwrap(), 1000);
+		assert!(reader.read_ranged(0u16, 1000).is_err());
+		```
+	*/
+	fn read_ranged<D: Deserialize<E, Self>+PartialOrd+Display>(&mut self, min: D, max: D) -> Res<D> { check_range(E::deserialize(self)?, min, max) }
+	/**
+		Reads a header written by `with_header`, returning its version, or an `InvalidData` error if the magic bytes don't match.
+
+		## Examples
+
+		```
+		use endio::LERead;
+
+		let mut reader = &b"ENDI\x02\x00\x2a"[..];
+		let version = reader.read_header(b"ENDI").unwrap();
+		assert_eq!(version, 2);
+		```
+	*/
+	fn read_header(&mut self, magic: &[u8]) -> Res<u16> where u8: Deserialize<E, Self>, u16: Deserialize<E, Self> { read_header::<E, _>(self, magic) }
+	/**
+		Reads a map like `read`, but preallocates space for at most `max_prealloc` entries instead of `MAP_PREALLOC_CAP`.
+
+		## Examples
+
+		```
+		use std::collections::HashMap;
+		use endio::LERead;
+
+		let mut reader = &b"\x01\x00\x00\x00\xad\xba\x2a"[..];
+		let map: HashMap<u16, u8> = reader.read_map_capped(16).unwrap();
+		assert_eq!(map[&0xbaad], 42);
+		```
+	*/
+	fn read_map_capped<K: Deserialize<E, Self>+Eq+Hash, V: Deserialize<E, Self>, S: BuildHasher+Default>(&mut self, max_prealloc: usize) -> Res<HashMap<K, V, S>> where Self: Read { read_map::<E, _, _, _, _>(self, max_prealloc) }
+	/**
+		Reads values into the buffer until it's full or the reader reaches its end, returning the number of values read.
+
+		Reaching the end between values is not an error, the remaining elements of the buffer are left untouched. If the reader ends partway through a value, the `UnexpectedEof` error is returned.
+
+		## Examples
+
+		```
+		use endio::LERead;
+
+		let mut reader = &b"\xad\xba\x0d\xf0"[..];
+		let mut buf = [0u16; 4];
+		assert_eq!(reader.read_filling(&mut buf).unwrap(), 2);
+		assert_eq!(buf, [0xbaad, 0xf00d, 0, 0]);
+		```
+	*/
+	fn read_filling<D>(&mut self, buf: &mut [D]) -> Res<usize> where Self: Read, D: for<'a> Deserialize<E, PositionTrackingReader<&'a mut Self>> { read_filling::<E, _, _>(self, buf) }
+	/**
+		Reads a string in the same format as `String`, handling invalid UTF-8 according to the policy.
+
+		## Examples
+
+		```
+		use endio::{LERead, Lossy};
+
+		let mut reader = &b"\x03\x00\x00\x00a\xffb"[..];
+		assert_eq!(reader.read_string::<Lossy>().unwrap(), "a\u{fffd}b");
+		```
+	*/
+	fn read_string<P: StringPolicy>(&mut self) -> Res<String> where Self: Read { read_string::<E, _, P>(self) }
+	/**
+		Reads a record of `len` bytes with the closure, then skips any bytes of the record the closure didn't read.
+
+		This allows newer writers to append fields to a record without breaking older readers, which only read the fields they know. The closure gets a reader limited to the record, so reading past its end is an `UnexpectedEof` error.
+
+		## Examples
+
+		```
+		use endio::LERead;
+
+		let mut reader = &b"\xad\xba\x2a\x2b\x0d\xf0"[..];
+		let known: u16 = reader.read_versioned(4, |r| r.eread()).unwrap();
+		assert_eq!(known, 0xbaad);
+		let next: u16 = reader.eread().unwrap();
+		assert_eq!(next, 0xf00d);
+		```
+	*/
+	fn read_versioned<T, F: FnOnce(&mut Take<&mut Self>) -> Res<T>>(&mut self, len: u64, body: F) -> Res<T> where Self: Read { read_versioned(self, len, body) }
+	/**
+		Reads a value that should take up exactly `size` bytes, returning an `InvalidData` error if its `Deserialize` read more or fewer.
+
+		Use this to catch custom `Deserialize` impls that read the wrong number of bytes for a field, which would otherwise misalign everything read after it. Note that the bytes are consumed either way.
+
+		## Examples
+
+		```
+		use endio::BERead;
+
+		let mut reader = &b"\xba\xad\xf0\x0d"[..];
+		let err = reader.read_checked::<u16>(4).unwrap_err();
+		assert_eq!(err.to_string(), "value was read from 2 bytes, expected 4");
+		```
+	*/
+	fn read_checked<'a, D: Deserialize<E, PositionTrackingReader<&'a mut Self>>>(&'a mut self, size: u64) -> Res<D> where Self: Read { read_checked::<E, _, _>(self, size) }
+	/// Reads a type that borrows from the reader's buffer. See `DeserializeBorrowed` for details.
+	fn read_borrowed<'a, D: DeserializeBorrowed<'a, E, Self>>(&mut self) -> Res<D> { D::deserialize_borrowed(self) }
+	/// Reads an `OptionOrDefault`, returning `default` if the value is absent.
+	fn read_option_or<D: Deserialize<E, Self>>(&mut self, default: D) -> Res<D> where u8: Deserialize<E, Self> { Ok(E::deserialize::<_, OptionOrDefault<D>>(self)?.0.unwrap_or(default)) }
 }
 
 /**
@@ -48,6 +216,15 @@ pub trait BERead: Sized {
 	fn eread   <D: Deserialize<BigEndian,    Self>>(&mut self) -> Res<D> { BigEndian::deserialize(self) }
 	fn read_be<D: Deserialize<BigEndian,    Self>>(&mut self) -> Res<D> { BigEndian::deserialize(self) }
 	fn read_le<D: Deserialize<LittleEndian, Self>>(&mut self) -> Res<D> { LittleEndian::deserialize(self) }
+	fn read_ranged<D: Deserialize<BigEndian, Self>+PartialOrd+Display>(&mut self, min: D, max: D) -> Res<D> { check_range(BigEndian::deserialize(self)?, min, max) }
+	fn read_header(&mut self, magic: &[u8]) -> Res<u16> where u8: Deserialize<BigEndian, Self>, u16: Deserialize<BigEndian, Self> { read_header::<BigEndian, _>(self, magic) }
+	fn read_map_capped<K: Deserialize<BigEndian, Self>+Eq+Hash, V: Deserialize<BigEndian, Self>, S: BuildHasher+Default>(&mut self, max_prealloc: usize) -> Res<HashMap<K, V, S>> where Self: Read { read_map::<BigEndian, _, _, _, _>(self, max_prealloc) }
+	fn read_filling<D>(&mut self, buf: &mut [D]) -> Res<usize> where Self: Read, D: for<'a> Deserialize<BigEndian, PositionTrackingReader<&'a mut Self>> { read_filling::<BigEndian, _, _>(self, buf) }
+	fn read_string<P: StringPolicy>(&mut self) -> Res<String> where Self: Read { read_string::<BigEndian, _, P>(self) }
+	fn read_versioned<T, F: FnOnce(&mut Take<&mut Self>) -> Res<T>>(&mut self, len: u64, body: F) -> Res<T> where Self: Read { read_versioned(self, len, body) }
+	fn read_checked<'a, D: Deserialize<BigEndian, PositionTrackingReader<&'a mut Self>>>(&'a mut self, size: u64) -> Res<D> where Self: Read { read_checked::<BigEndian, _, _>(self, size) }
+	fn read_borrowed<'a, D: DeserializeBorrowed<'a, BigEndian, Self>>(&mut self) -> Res<D> { D::deserialize_borrowed(self) }
+	fn read_option_or<D: Deserialize<BigEndian, Self>>(&mut self, default: D) -> Res<D> where u8: Deserialize<BigEndian, Self> { Ok(BigEndian::deserialize::<_, OptionOrDefault<D>>(self)?.0.unwrap_or(default)) }
 }
 
 /**
@@ -61,6 +238,15 @@ pub trait LERead: Sized {
 	fn eread   <D: Deserialize<LittleEndian, Self>>(&mut self) -> Res<D> { LittleEndian::deserialize(self) }
 	fn read_be<D: Deserialize<BigEndian,    Self>>(&mut self) -> Res<D> { BigEndian::deserialize(self) }
 	fn read_le<D: Deserialize<LittleEndian, Self>>(&mut self) -> Res<D> { LittleEndian::deserialize(self) }
+	fn read_ranged<D: Deserialize<LittleEndian, Self>+PartialOrd+Display>(&mut self, min: D, max: D) -> Res<D> { check_range(LittleEndian::deserialize(self)?, min, max) }
+	fn read_header(&mut self, magic: &[u8]) -> Res<u16> where u8: Deserialize<LittleEndian, Self>, u16: Deserialize<LittleEndian, Self> { read_header::<LittleEndian, _>(self, magic) }
+	fn read_map_capped<K: Deserialize<LittleEndian, Self>+Eq+Hash, V: Deserialize<LittleEndian, Self>, S: BuildHasher+Default>(&mut self, max_prealloc: usize) -> Res<HashMap<K, V, S>> where Self: Read { read_map::<LittleEndian, _, _, _, _>(self, max_prealloc) }
+	fn read_filling<D>(&mut self, buf: &mut [D]) -> Res<usize> where Self: Read, D: for<'a> Deserialize<LittleEndian, PositionTrackingReader<&'a mut Self>> { read_filling::<LittleEndian, _, _>(self, buf) }
+	fn read_string<P: StringPolicy>(&mut self) -> Res<String> where Self: Read { read_string::<LittleEndian, _, P>(self) }
+	fn read_versioned<T, F: FnOnce(&mut Take<&mut Self>) -> Res<T>>(&mut self, len: u64, body: F) -> Res<T> where Self: Read { read_versioned(self, len, body) }
+	fn read_checked<'a, D: Deserialize<LittleEndian, PositionTrackingReader<&'a mut Self>>>(&'a mut self, size: u64) -> Res<D> where Self: Read { read_checked::<LittleEndian, _, _>(self, size) }
+	fn read_borrowed<'a, D: DeserializeBorrowed<'a, LittleEndian, Self>>(&mut self) -> Res<D> { D::deserialize_borrowed(self) }
+	fn read_option_or<D: Deserialize<LittleEndian, Self>>(&mut self, default: D) -> Res<D> where u8: Deserialize<LittleEndian, Self> { Ok(LittleEndian::deserialize::<_, OptionOrDefault<D>>(self)?.0.unwrap_or(default)) }
 }
 
 impl<R: Read, E: Endianness> ERead<E> for R {}
@@ -68,6 +254,7 @@ impl<R: Read> BERead for R {}
 impl<R: Read> LERead for R {}
 
 #[cfg(test)]
+#[allow(clippy::redundant_slicing)]
 mod tests {
 	const DATA: &[u8] = b"\xba\xad";
 
@@ -86,4 +273,160 @@ mod tests {
 		let val: u16 = reader.read_le().unwrap();
 		assert_eq!(val, 0xadba);
 	}
+
+	#[test]
+	fn read_dyn() {
+		use std::io::Read;
+		use crate::BERead;
+		let mut data = &b"\xba\xad\x00\x00\x00\x02io"[..];
+		let mut reader: &mut dyn Read = &mut data;
+		let val: u16 = reader.eread().unwrap();
+		assert_eq!(val, 0xbaad);
+		let val: String = reader.eread().unwrap();
+		assert_eq!(val, "io");
+		let mut reader: Box<dyn Read> = Box::new(DATA);
+		let val: u16 = reader.eread().unwrap();
+		assert_eq!(val, 0xbaad);
+	}
+
+	#[test]
+	fn read_ranged_in_range() {
+		use crate::BERead;
+		let mut reader = DATA;
+		let val: u16 = reader.read_ranged(0xbaad, 0xffff).unwrap();
+		assert_eq!(val, 0xbaad);
+	}
+
+	#[test]
+	fn read_ranged_out_of_range() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = DATA;
+		let err = reader.read_ranged(0u16, 1000).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "value 44474 out of range 0..=1000");
+	}
+
+	#[test]
+	fn read_header() {
+		use crate::BERead;
+		let mut reader = &b"ENDI\xba\xad\xf0\x0d"[..];
+		let version = reader.read_header(b"ENDI").unwrap();
+		assert_eq!(version, 0xbaad);
+		let body: u16 = reader.eread().unwrap();
+		assert_eq!(body, 0xf00d);
+	}
+
+	#[test]
+	fn read_header_wrong_magic() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"ENDO\xba\xad"[..];
+		let err = reader.read_header(b"ENDI").unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "header had magic [45, 4e, 44, 4f], expected [45, 4e, 44, 49]");
+	}
+
+	#[test]
+	fn read_map_capped() {
+		use std::collections::HashMap;
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x02\xba\xad\x2a\xf0\x0d\x2b"[..];
+		let map: HashMap<u16, u8> = reader.read_map_capped(1).unwrap();
+		assert_eq!(map.len(), 2);
+		assert_eq!(map[&0xbaad], 42);
+		assert_eq!(map[&0xf00d], 43);
+	}
+
+	#[test]
+	fn read_filling_partial() {
+		use crate::BERead;
+		let mut reader = &b"\xba\xad\xf0\x0d"[..];
+		let mut buf = [0u16; 3];
+		assert_eq!(reader.read_filling(&mut buf).unwrap(), 2);
+		assert_eq!(buf, [0xbaad, 0xf00d, 0]);
+		assert_eq!(reader.read_filling(&mut buf).unwrap(), 0);
+	}
+
+	#[test]
+	fn read_filling_full() {
+		use crate::LERead;
+		let mut reader = &b"\x01\x02\x03"[..];
+		let mut buf = [0u8; 2];
+		assert_eq!(reader.read_filling(&mut buf).unwrap(), 2);
+		assert_eq!(buf, [1, 2]);
+		assert_eq!(reader, b"\x03");
+	}
+
+	#[test]
+	fn read_filling_partway() {
+		use std::io;
+		use crate::BERead;
+		let mut reader = &b"\xba\xad\xf0"[..];
+		let mut buf = [0u16; 2];
+		let err = reader.read_filling(&mut buf).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		assert_eq!(buf, [0xbaad, 0]);
+	}
+
+	#[test]
+	fn read_filling_error() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x01\x02"[..];
+		let mut buf = [false; 2];
+		let err = reader.read_filling(&mut buf).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(buf, [true, false]);
+	}
+
+	#[test]
+	fn read_versioned_skips_unknown() {
+		use crate::BERead;
+		let mut reader = &b"\xba\xad\x01\xff\xfe\x2a"[..];
+		let known: (u16, bool) = reader.read_versioned(5, |r| r.eread()).unwrap();
+		assert_eq!(known, (0xbaad, true));
+		let next: u8 = reader.eread().unwrap();
+		assert_eq!(next, 42);
+	}
+
+	#[test]
+	fn read_versioned_errors() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\xad\xba\x0d\xf0"[..];
+		let err = reader.read_versioned(2, |r| r.eread::<u32>()).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		let mut reader = &b"\xad\xba\x0d"[..];
+		let err = reader.read_versioned(4, |r| r.eread::<u16>()).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+		assert_eq!(err.to_string(), "record ended before its declared length");
+	}
+
+	#[test]
+	fn read_checked_catches_under_read() {
+		use std::io;
+		use std::io::Read;
+		use crate::{BERead, Deserialize, Endianness};
+
+		/// Declares a `u32` field, but only reads a `u16` of it.
+		#[derive(Debug)]
+		struct Buggy;
+
+		impl<E: Endianness, R: Read> Deserialize<E, R> for Buggy {
+			fn deserialize(reader: &mut R) -> io::Result<Self> {
+				let _: u16 = E::deserialize(reader)?;
+				Ok(Buggy)
+			}
+		}
+
+		let mut reader = &b"\x00\x00\xba\xad\x2a"[..];
+		let err = reader.read_checked::<Buggy>(4).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "value was read from 2 bytes, expected 4");
+		let mut reader = &b"\x00\x00\xba\xad\x2a"[..];
+		let val: u32 = reader.read_checked(4).unwrap();
+		assert_eq!(val, 0xbaad);
+		assert_eq!(reader, b"\x2a");
+	}
 }
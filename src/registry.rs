@@ -0,0 +1,205 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+use std::marker::PhantomData;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::invalid_data;
+
+type SerializeFn = fn(&dyn Any, &mut dyn Write) -> Res<()>;
+type DeserializeFn<T> = Box<dyn Fn(&mut dyn Read) -> Res<Box<T>>>;
+
+/**
+	(De-)serializes trait objects, by tagging each value with a `u32` identifying its concrete type.
+
+	Every concrete type is registered with a tag, which is written before the value's data. When reading, the tag selects which type to deserialize. Both sides need to register the same types with the same tags.
+
+	`T` is the trait object type, such as `dyn Shape`. To find a value's concrete type, the registry needs to view it as `dyn Any`, so the trait should have `Any` as a supertrait.
+
+	## Examples
+
+	```
+	use std::any::Any;
+	use endio::{LittleEndian, Registry};
+
+	trait Shape: Any {
+		fn area(&self) -> f32;
+	}
+
+	impl Shape for f32 {
+		fn area(&self) -> f32 { *self }
+	}
+
+	let mut registry = Registry::<dyn Shape, LittleEndian>::new(|shape| shape as &dyn Any);
+	registry.register::<f32>(1, |area| Box::new(area));
+
+	let mut writer = vec![];
+	registry.write(&mut writer, &42f32).unwrap();
+	assert_eq!(writer, b"\x01\x00\x00\x00\x00\x00\x28\x42");
+
+	let mut reader = &writer[..];
+	let shape = registry.read(&mut reader).unwrap();
+	assert_eq!(shape.area(), 42.0);
+	```
+*/
+pub struct Registry<T: ?Sized, E> {
+	as_any: fn(&T) -> &dyn Any,
+	serializers: HashMap<TypeId, (u32, SerializeFn)>,
+	deserializers: HashMap<u32, DeserializeFn<T>>,
+	endianness: PhantomData<E>,
+}
+
+fn serialize_any<E: Endianness, C: Any>(value: &dyn Any, mut writer: &mut dyn Write) -> Res<()> where for<'a, 'b> &'a C: Serialize<E, &'b mut dyn Write> {
+	let value: &C = value.downcast_ref().expect("serializer registered for a different type");
+	E::serialize(value, &mut writer)
+}
+
+impl<T: ?Sized+'static, E: Endianness> Registry<T, E> {
+	/// Creates an empty registry, viewing trait objects as `dyn Any` through `as_any`.
+	pub fn new(as_any: fn(&T) -> &dyn Any) -> Self {
+		Self { as_any, serializers: HashMap::new(), deserializers: HashMap::new(), endianness: PhantomData }
+	}
+
+	/**
+		Registers the concrete type `C` under `tag`, with `upcast` converting it to the trait object.
+
+		## Panics
+
+		Panics if the tag or the type is already registered.
+	*/
+	pub fn register<C>(&mut self, tag: u32, upcast: fn(C) -> Box<T>)
+	where for<'a, 'b> &'a C: Serialize<E, &'b mut dyn Write>, C: Any+for<'b> Deserialize<E, &'b mut dyn Read> {
+		assert!(!self.deserializers.contains_key(&tag), "tag {} is already registered", tag);
+		assert!(!self.serializers.contains_key(&TypeId::of::<C>()), "type is already registered");
+		self.serializers.insert(TypeId::of::<C>(), (tag, serialize_any::<E, C>));
+		self.deserializers.insert(tag, Box::new(move |mut reader: &mut dyn Read| Ok(upcast(E::deserialize(&mut reader)?))));
+	}
+
+	/// Writes the value's tag, followed by the value. Returns an `InvalidInput` error if the value's type isn't registered.
+	pub fn write<W: Write>(&self, writer: &mut W, value: &T) -> Res<()> {
+		let value = (self.as_any)(value);
+		let (tag, serialize) = self.serializers.get(&value.type_id())
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "type is not registered"))?;
+		E::serialize(*tag, writer)?;
+		serialize(value, writer)
+	}
+
+	/// Reads a tag, followed by a value of the type registered for it. Returns an `InvalidData` error if the tag isn't registered.
+	pub fn read<R: Read>(&self, reader: &mut R) -> Res<Box<T>> {
+		let tag: u32 = E::deserialize(reader)?;
+		let deserialize = self.deserializers.get(&tag)
+			.ok_or_else(|| invalid_data(format!("tag {} is not registered", tag)))?;
+		deserialize(reader)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::any::Any;
+	use std::io;
+	use std::io::{Read, Write};
+	use std::io::Result as Res;
+	use crate::{BigEndian, Deserialize, Endianness, Serialize};
+	use super::Registry;
+
+	trait Shape: Any {
+		fn area(&self) -> f64;
+	}
+
+	#[derive(Debug, PartialEq)]
+	struct Square(u16);
+
+	#[derive(Debug, PartialEq)]
+	struct Rect(u16, u16);
+
+	impl Shape for Square {
+		fn area(&self) -> f64 { f64::from(self.0).powi(2) }
+	}
+
+	impl Shape for u8 {
+		fn area(&self) -> f64 { 0.0 }
+	}
+
+	impl Shape for Rect {
+		fn area(&self) -> f64 { f64::from(self.0) * f64::from(self.1) }
+	}
+
+	impl<E: Endianness, W: Write> Serialize<E, W> for &Square where u16: Serialize<E, W> {
+		fn serialize(self, writer: &mut W) -> Res<()> {
+			E::serialize(self.0, writer)
+		}
+	}
+
+	impl<E: Endianness, R: Read> Deserialize<E, R> for Square where u16: Deserialize<E, R> {
+		fn deserialize(reader: &mut R) -> Res<Self> {
+			Ok(Square(E::deserialize(reader)?))
+		}
+	}
+
+	impl<E: Endianness, W: Write> Serialize<E, W> for &Rect where u16: Serialize<E, W> {
+		fn serialize(self, writer: &mut W) -> Res<()> {
+			E::serialize(self.0, writer)?;
+			E::serialize(self.1, writer)
+		}
+	}
+
+	impl<E: Endianness, R: Read> Deserialize<E, R> for Rect where u16: Deserialize<E, R> {
+		fn deserialize(reader: &mut R) -> Res<Self> {
+			Ok(Rect(E::deserialize(reader)?, E::deserialize(reader)?))
+		}
+	}
+
+	fn registry() -> Registry<dyn Shape, BigEndian> {
+		let mut registry = Registry::<dyn Shape, BigEndian>::new(|shape| shape as &dyn Any);
+		registry.register::<Square>(1, |square| Box::new(square));
+		registry.register::<Rect>(2, |rect| Box::new(rect));
+		registry
+	}
+
+	#[test]
+	fn round_trip() {
+		let registry = registry();
+		let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(3)), Box::new(Rect(2, 5)), Box::new(Square(4))];
+		let mut writer = vec![];
+		for shape in &shapes {
+			registry.write(&mut writer, &**shape).unwrap();
+		}
+		assert_eq!(writer, b"\x00\x00\x00\x01\x00\x03\x00\x00\x00\x02\x00\x02\x00\x05\x00\x00\x00\x01\x00\x04");
+
+		let mut reader = &writer[..];
+		let mut read: Vec<Box<dyn Shape>> = vec![];
+		for _ in 0..shapes.len() {
+			read.push(registry.read(&mut reader).unwrap());
+		}
+		assert!(reader.is_empty());
+		let areas: Vec<f64> = read.iter().map(|shape| shape.area()).collect();
+		assert_eq!(areas, [9.0, 10.0, 16.0]);
+		let any: &dyn Any = &*read[1];
+		assert_eq!(any.downcast_ref::<Rect>(), Some(&Rect(2, 5)));
+	}
+
+	#[test]
+	fn unregistered_type() {
+		let mut writer = vec![];
+		let err = registry().write(&mut writer, &0u8).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(writer.is_empty());
+	}
+
+	#[test]
+	fn unknown_tag() {
+		let mut reader = &b"\x00\x00\x00\x03\x00\x01"[..];
+		let err = registry().read(&mut reader).err().unwrap();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "tag 3 is not registered");
+	}
+
+	#[test]
+	#[should_panic(expected = "tag 1 is already registered")]
+	fn duplicate_tag() {
+		let mut registry = registry();
+		registry.register::<u8>(1, |area| Box::new(area));
+	}
+}
@@ -0,0 +1,120 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{invalid_data, read_len};
+use crate::serialize::write_len;
+
+/**
+	Bytes compressed with run-length encoding.
+
+	Written as a `u32` prefix of the decoded length, followed by a `(count, value)` pair of `u8`s for each run of equal bytes. Runs longer than 255 bytes are split into several pairs. Note that data without runs takes up twice its size.
+
+	Any `T: AsRef<[u8]>` can be written, and `Vec<u8>` can be read. Reading returns an `InvalidData` error if a run has a count of 0 or goes past the decoded length.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, RleBytes};
+
+	let mut writer = vec![];
+	writer.ewrite(RleBytes(b"aaaab")).unwrap();
+	assert_eq!(writer, b"\x05\x00\x00\x00\x04a\x01b");
+
+	let mut reader = &writer[..];
+	let RleBytes(bytes): RleBytes<Vec<u8>> = reader.eread().unwrap();
+	assert_eq!(bytes, b"aaaab");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RleBytes<T>(pub T);
+
+impl<E: Endianness, W: Write, T: AsRef<[u8]>> Serialize<E, W> for RleBytes<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let bytes = self.0.as_ref();
+		write_len::<E, W>(writer, bytes.len())?;
+		let mut encoded = vec![];
+		let mut rest = bytes;
+		while let Some(&value) = rest.first() {
+			let count = rest.iter().take(255).take_while(|&&b| b == value).count();
+			encoded.push(count as u8);
+			encoded.push(value);
+			rest = &rest[count..];
+		}
+		writer.write_all(&encoded)
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for RleBytes<Vec<u8>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let len = read_len::<E, R>(reader)?;
+		let mut bytes = vec![];
+		while bytes.len() < len {
+			let mut run = [0; 2];
+			reader.read_exact(&mut run)?;
+			let [count, value] = run;
+			if count == 0 {
+				return Err(invalid_data("run had a count of 0".into()));
+			}
+			if bytes.len() + count as usize > len {
+				return Err(invalid_data(format!("runs decode to more than {} bytes", len)));
+			}
+			bytes.resize(bytes.len() + count as usize, value);
+		}
+		Ok(RleBytes(bytes))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::RleBytes;
+
+	#[test]
+	fn runs() {
+		use crate::{BERead, BEWrite};
+		let mut data = vec![0u8; 300];
+		data.extend_from_slice(&[0xff; 3]);
+		data.push(0x2a);
+		let mut writer = vec![];
+		writer.ewrite(RleBytes(&data)).unwrap();
+		assert_eq!(writer, b"\x00\x00\x01\x30\xff\x00\x2d\x00\x03\xff\x01\x2a");
+		let mut reader = &writer[..];
+		let val: RleBytes<Vec<u8>> = reader.eread().unwrap();
+		assert_eq!(val.0, data);
+	}
+
+	#[test]
+	fn incompressible() {
+		use crate::{LERead, LEWrite};
+		let data: Vec<u8> = (0..=255).collect();
+		let mut writer = vec![];
+		writer.ewrite(RleBytes(&data)).unwrap();
+		assert_eq!(writer.len(), 4 + 2 * data.len());
+		let mut reader = &writer[..];
+		let val: RleBytes<Vec<u8>> = reader.eread().unwrap();
+		assert_eq!(val.0, data);
+	}
+
+	#[test]
+	fn empty() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(RleBytes(b"")).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+		let mut reader = &writer[..];
+		let val: RleBytes<Vec<u8>> = reader.eread().unwrap();
+		assert!(val.0.is_empty());
+	}
+
+	#[test]
+	fn invalid_runs() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x02\x00\x00\x00\x00\x2a"[..];
+		let err = reader.eread::<RleBytes<Vec<u8>>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		let mut reader = &b"\x02\x00\x00\x00\x03\x2a"[..];
+		let err = reader.eread::<RleBytes<Vec<u8>>>().unwrap_err();
+		assert_eq!(err.to_string(), "runs decode to more than 2 bytes");
+	}
+}
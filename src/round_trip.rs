@@ -0,0 +1,101 @@
+//! Property tests checking that everything written can be read back unchanged, in both endiannesses.
+
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use crate::{BigEndian, Deserialize, Endianness, LittleEndian, Serialize};
+
+fn round_trip<E: Endianness, S: Serialize<E, Vec<u8>>, D: for<'a> Deserialize<E, &'a [u8]>>(value: S) -> D {
+	let mut writer = vec![];
+	E::serialize(value, &mut writer).unwrap();
+	let mut reader = &writer[..];
+	let read = E::deserialize(&mut reader).unwrap();
+	assert!(reader.is_empty(), "{} bytes left over", reader.len());
+	read
+}
+
+macro_rules! round_trip_value {
+	($name:ident, $t:ty) => {
+		mod $name {
+			use super::*;
+
+			proptest! {
+				#[test]
+				fn be(value: $t) {
+					prop_assert_eq!(round_trip::<BigEndian, _, $t>(value), value);
+				}
+
+				#[test]
+				fn le(value: $t) {
+					prop_assert_eq!(round_trip::<LittleEndian, _, $t>(value), value);
+				}
+			}
+		}
+	}
+}
+
+round_trip_value!(bool, bool);
+round_trip_value!(u8, u8);
+round_trip_value!(u16, u16);
+round_trip_value!(u32, u32);
+round_trip_value!(u64, u64);
+round_trip_value!(u128, u128);
+round_trip_value!(i8, i8);
+round_trip_value!(i16, i16);
+round_trip_value!(i32, i32);
+round_trip_value!(i64, i64);
+round_trip_value!(i128, i128);
+round_trip_value!(char, char);
+round_trip_value!(tuple, (u8, i32, bool, u64));
+
+// floats are compared by their bits, so NaNs round trip too
+macro_rules! round_trip_float {
+	($name:ident, $t:ident, $bits:ty) => {
+		mod $name {
+			use super::*;
+
+			proptest! {
+				#[test]
+				fn be(bits: $bits) {
+					let value = $t::from_bits(bits);
+					prop_assert_eq!(round_trip::<BigEndian, _, $t>(value).to_bits(), bits);
+				}
+
+				#[test]
+				fn le(bits: $bits) {
+					let value = $t::from_bits(bits);
+					prop_assert_eq!(round_trip::<LittleEndian, _, $t>(value).to_bits(), bits);
+				}
+			}
+		}
+	}
+}
+
+round_trip_float!(f32, f32, u32);
+round_trip_float!(f64, f64, u64);
+
+// collections are written by reference
+macro_rules! round_trip_ref {
+	($name:ident, $t:ty, $strategy:expr) => {
+		mod $name {
+			use super::*;
+
+			proptest! {
+				#[test]
+				fn be(value in $strategy) {
+					prop_assert_eq!(round_trip::<BigEndian, _, $t>(&value), value);
+				}
+
+				#[test]
+				fn le(value in $strategy) {
+					prop_assert_eq!(round_trip::<LittleEndian, _, $t>(&value), value);
+				}
+			}
+		}
+	}
+}
+
+round_trip_ref!(string, String, any::<String>());
+round_trip_ref!(boxed_slice, Box<[u32]>, prop::collection::vec(any::<u32>(), 0..64).prop_map(Vec::into_boxed_slice));
+round_trip_ref!(hash_map, HashMap<u16, String>, prop::collection::hash_map(any::<u16>(), any::<String>(), 0..16));
@@ -0,0 +1,124 @@
+use std::io::Result as Res;
+use std::marker::PhantomData;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+mod private {
+	/// Integers that can be converted between each other with saturation.
+	pub trait SaturatingInt: Copy {
+		/// The value as a `u128` if it's non-negative, otherwise as a negative `i128`.
+		fn to_parts(self) -> Result<u128, i128>;
+		fn from_parts(parts: Result<u128, i128>) -> Self;
+	}
+}
+
+use self::private::SaturatingInt;
+
+macro_rules! impl_saturating_int {
+	($($t:ident)*) => { $(
+		impl SaturatingInt for $t {
+			#[allow(unused_comparisons)]
+			fn to_parts(self) -> Result<u128, i128> {
+				if self < 0 { Err(self as i128) } else { Ok(self as u128) }
+			}
+
+			#[allow(unused_comparisons)]
+			fn from_parts(parts: Result<u128, i128>) -> Self {
+				match parts {
+					Ok(value) if value > $t::MAX as u128 => $t::MAX,
+					Ok(value) => value as $t,
+					Err(value) if value < $t::MIN as i128 => $t::MIN,
+					Err(value) => value as $t,
+				}
+			}
+		}
+	)* }
+}
+
+impl_saturating_int!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+
+/**
+	An integer `T` that's read/written as the integer type `P`, clamping values that don't fit into the other type to its bounds.
+
+	This is lossy: A value out of `T`'s range is read as `T::MIN` or `T::MAX` instead of returning an error, and the same happens when writing a value out of `P`'s range. Use it for lenient parsing of noisy data, where a clamped value is more useful than failing. Use a plain `P` and `TryFrom` if out-of-range values should be rejected instead.
+
+	## Examples
+
+	```
+	use endio::{LERead, SaturatingRead};
+
+	let mut reader = &b"\x2c\x01\x00\x00\xfb\xff\xff\xff\x2a\x00\x00\x00"[..];
+	let values: Vec<u8> = (0..3).map(|_| reader.eread::<SaturatingRead<i32, u8>>().unwrap().value).collect();
+	assert_eq!(values, [255, 0, 42]);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SaturatingRead<P, T> {
+	pub value: T,
+	wire: PhantomData<P>,
+}
+
+impl<P, T> SaturatingRead<P, T> {
+	pub fn new(value: T) -> Self {
+		Self { value, wire: PhantomData }
+	}
+}
+
+impl<E: Endianness, W, P: SaturatingInt+Serialize<E, W>, T: SaturatingInt> Serialize<E, W> for SaturatingRead<P, T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(P::from_parts(self.value.to_parts()), writer)
+	}
+}
+
+impl<E: Endianness, R, P: SaturatingInt+Deserialize<E, R>, T: SaturatingInt> Deserialize<E, R> for SaturatingRead<P, T> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let wire: P = E::deserialize(reader)?;
+		Ok(Self::new(T::from_parts(wire.to_parts())))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::SaturatingRead;
+
+	#[test]
+	fn over_range_clamps() {
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x01\x2c\xff\xff\xff\xfb"[..];
+		let val: SaturatingRead<i32, u8> = reader.eread().unwrap();
+		assert_eq!(val.value, u8::MAX);
+		let val: SaturatingRead<i32, u8> = reader.eread().unwrap();
+		assert_eq!(val.value, u8::MIN);
+		let mut reader = &b"\xff\xff\xff\xff\xff\xff\xff\xff"[..];
+		let val: SaturatingRead<u64, i8> = reader.eread().unwrap();
+		assert_eq!(val.value, i8::MAX);
+		let mut reader = &b"\x80\x00\x00\x00\x00\x00\x00\x00"[..];
+		let val: SaturatingRead<i64, i16> = reader.eread().unwrap();
+		assert_eq!(val.value, i16::MIN);
+	}
+
+	#[test]
+	fn in_range_unchanged() {
+		use crate::{LERead, LEWrite};
+		for value in [-128i16, -1, 0, 42, 127] {
+			let mut writer = vec![];
+			writer.ewrite(SaturatingRead::<i8, i16>::new(value)).unwrap();
+			assert_eq!(writer, [value as u8]);
+			let mut reader = &writer[..];
+			let val: SaturatingRead<i8, i16> = reader.eread().unwrap();
+			assert_eq!(val.value, value);
+		}
+		let mut reader = &b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff"[..];
+		let val: SaturatingRead<u128, u128> = reader.eread().unwrap();
+		assert_eq!(val.value, u128::MAX);
+	}
+
+	#[test]
+	fn write_clamps() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.ewrite(SaturatingRead::<i8, u8>::new(200)).unwrap();
+		writer.ewrite(SaturatingRead::<u16, i32>::new(-5)).unwrap();
+		assert_eq!(writer, b"\x7f\x00\x00");
+	}
+}
@@ -0,0 +1,127 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::config::check_len;
+use crate::deserialize::{invalid_data, read_bytes};
+
+/**
+	A value with a length prefix of type `P` that counts the prefix itself, as used for total frame lengths.
+
+	The prefix is the width of `P` plus the length of the serialized value. When reading, the width is subtracted again, and the value has to take up exactly the remaining bytes. Prefixes smaller than the width of `P` and leftover bytes are `InvalidData` errors.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, SelfInclusiveLength};
+
+	let mut writer = vec![];
+	writer.ewrite(SelfInclusiveLength::<u16, _>::new(0xbaadf00du32)).unwrap();
+	assert_eq!(writer, b"\x06\x00\x0d\xf0\xad\xba");
+
+	let mut reader = &writer[..];
+	let frame: SelfInclusiveLength<u16, u32> = reader.eread().unwrap();
+	assert_eq!(frame.value, 0xbaadf00d);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SelfInclusiveLength<P, T> {
+	pub value: T,
+	prefix: PhantomData<P>,
+}
+
+impl<P, T> SelfInclusiveLength<P, T> {
+	pub fn new(value: T) -> Self {
+		Self { value, prefix: PhantomData }
+	}
+}
+
+impl<E: Endianness, W: Write, P: TryFrom<usize>+Serialize<E, W>, T: Serialize<E, Vec<u8>>> Serialize<E, W> for SelfInclusiveLength<P, T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let mut body = vec![];
+		E::serialize(self.value, &mut body)?;
+		let len = size_of::<P>() + body.len();
+		let prefix = P::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("length {} does not fit in prefix", len)))?;
+		E::serialize(prefix, writer)?;
+		writer.write_all(&body)
+	}
+}
+
+impl<E: Endianness, R: Read, P: Deserialize<E, R>, T: for<'a> Deserialize<E, &'a [u8]>> Deserialize<E, R> for SelfInclusiveLength<P, T> where usize: TryFrom<P> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let prefix: P = E::deserialize(reader)?;
+		let len = usize::try_from(prefix).ok()
+			.and_then(|len| len.checked_sub(size_of::<P>()))
+			.ok_or_else(|| invalid_data(format!("length prefix is smaller than its own width of {}", size_of::<P>())))?;
+		let body = read_bytes(reader, check_len(len)?)?;
+		let mut body = &body[..];
+		let value = E::deserialize(&mut body)?;
+		if !body.is_empty() {
+			return Err(invalid_data(format!("body had {} trailing bytes", body.len())));
+		}
+		Ok(Self::new(value))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::SelfInclusiveLength;
+
+	#[test]
+	fn round_trip() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(SelfInclusiveLength::<u32, _>::new("endio")).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x0d\x00\x00\x00\x05endio");
+		let mut reader = &writer[..];
+		let frame: SelfInclusiveLength<u32, String> = reader.eread().unwrap();
+		assert_eq!(frame.value, "endio");
+	}
+
+	#[test]
+	fn empty_body() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(SelfInclusiveLength::<u16, [u8; 0]>::new([])).unwrap();
+		assert_eq!(writer, b"\x02\x00");
+		let mut reader = &writer[..];
+		let frame: SelfInclusiveLength<u16, [u8; 0]> = reader.eread().unwrap();
+		assert_eq!(frame.value, []);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn prefix_smaller_than_width() {
+		use crate::LERead;
+		let mut reader = &b"\x01\x00"[..];
+		let err = reader.eread::<SelfInclusiveLength<u16, [u8; 0]>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "length prefix is smaller than its own width of 2");
+	}
+
+	#[test]
+	fn wrong_body_len() {
+		use crate::LERead;
+		let mut reader = &b"\x04\x2a\x2b\x2c"[..];
+		let err = reader.eread::<SelfInclusiveLength<u8, u16>>().unwrap_err();
+		assert_eq!(err.to_string(), "body had 1 trailing bytes");
+		let mut reader = &b"\x02\x2a"[..];
+		let err = reader.eread::<SelfInclusiveLength<u8, u16>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn too_long() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		let err = writer.ewrite(SelfInclusiveLength::<u8, _>::new([0u8; 255])).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "length 256 does not fit in prefix");
+	}
+}
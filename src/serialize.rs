@@ -1,5 +1,19 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::{Infallible, TryFrom};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io;
+use std::io::{Read, Write};
 use std::io::Result as Res;
-use std::io::Write;
+use std::mem::size_of;
+use std::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroU8};
+use std::ops::{ControlFlow, Range, RangeFrom, RangeFull, RangeTo};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{self, AtomicBool};
 
 use crate::{Endianness, EWrite};
 
@@ -75,6 +89,41 @@ use crate::{Endianness, EWrite};
 	}
 	```
 
+	### Serialize a type that's the same in both endiannesses:
+
+	Byte-oriented types like tags or UTF-8 text don't depend on endianness. Implement only `serialize`, generic over `E`, and both `serialize_be` and `serialize_le` will use it. The same goes for `Deserialize::deserialize`. There's no marker trait for this, as a blanket impl over such a trait would conflict with the impls for references and arrays.
+	```
+	use std::io::{Read, Result, Write};
+	use endio::{BERead, BEWrite, Deserialize, Endianness, LERead, LEWrite, Serialize};
+
+	#[derive(Debug, PartialEq)]
+	struct FourCc([u8; 4]);
+
+	impl<E: Endianness, W: Write> Serialize<E, W> for &FourCc {
+		fn serialize(self, writer: &mut W) -> Result<()> {
+			writer.write_all(&self.0)
+		}
+	}
+
+	impl<E: Endianness, R: Read> Deserialize<E, R> for FourCc {
+		fn deserialize(reader: &mut R) -> Result<Self> {
+			let mut buf = [0; 4];
+			reader.read_exact(&mut buf)?;
+			Ok(FourCc(buf))
+		}
+	}
+
+	let tag = FourCc(*b"RIFF");
+	let mut writer = vec![];
+	BEWrite::ewrite(&mut writer, &tag).unwrap();
+	LEWrite::ewrite(&mut writer, &tag).unwrap();
+	assert_eq!(writer, b"RIFFRIFF");
+
+	let mut reader = &writer[..];
+	assert_eq!(BERead::eread::<FourCc>(&mut reader).unwrap(), tag);
+	assert_eq!(LERead::eread::<FourCc>(&mut reader).unwrap(), tag);
+	```
+
 	### Serialize with endian-specific code:
 
 	Note how instead of using a trait bound on Endianness, we implement Serialize twice, once for `BigEndian` and once for `LittleEndian`.
@@ -93,148 +142,1084 @@ use crate::{Endianness, EWrite};
 		}
 	}
 
-	impl<W: Write> Serialize<LittleEndian, W> for new_u16 {
-		fn serialize(self, writer: &mut W) -> Result<()> {
-			writer.write_all(&self.0.to_le_bytes())?;
-			Ok(())
-		}
+	impl<W: Write> Serialize<LittleEndian, W> for new_u16 {
+		fn serialize(self, writer: &mut W) -> Result<()> {
+			writer.write_all(&self.0.to_le_bytes())?;
+			Ok(())
+		}
+	}
+	```
+*/
+pub trait Serialize<E: Endianness, W>: Sized {
+	/// The number of bytes every value of this type is written as, if that's fixed. `write_checked` asserts this in debug builds.
+	const SIZE: Option<usize> = None;
+
+	/// Serializes the type by writing to the writer using Big-endian.
+	/// Implement ONLY this method if your code for both endianness is the same.
+	fn serialize(self, _writer: &mut W) -> Res<()> {
+		unreachable!();
+	}
+
+	/// Serializes the type by writing to the writer using Big-endian.
+	fn serialize_be(self, writer: &mut W) -> Res<()> {
+		self.serialize(writer)
+	}
+
+	/// Serializes the type by writing to the writer using Little-endian.
+	fn serialize_le(self, writer: &mut W) -> Res<()> {
+		self.serialize(writer)
+	}
+}
+
+/// Writes the value behind the mutable reference, the same way as through a shared reference.
+impl<E: Endianness, W, S: ?Sized> Serialize<E, W> for &mut S where for<'a> &'a S: Serialize<E, W> {
+	const SIZE: Option<usize> = <&S as Serialize<E, W>>::SIZE;
+
+	fn serialize_be(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize_be(&*self, writer)
+	}
+
+	fn serialize_le(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize_le(&*self, writer)
+	}
+}
+
+/// The total of the sizes, `count` times over, or `None` if any of them is `None`.
+const fn sum_sizes(sizes: &[Option<usize>], count: usize) -> Option<usize> {
+	let mut total = 0;
+	let mut i = 0;
+	while i < sizes.len() {
+		match sizes[i] {
+			Some(size) => total += size,
+			None => return None,
+		}
+		i += 1;
+	}
+	Some(total * count)
+}
+
+/// Writes the `u32` length prefix used by length-prefixed types, returning an `InvalidInput` error if the length doesn't fit.
+pub(crate) fn write_len<E: Endianness, W: Write>(writer: &mut W, len: usize) -> Res<()> {
+	let len = u32::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length does not fit in u32 prefix"))?;
+	E::serialize(len, writer)
+}
+
+// todo[specialization]: specialize for &[u8] (std::io::Write::write_all)
+/// Writes the entire contents of the byte slice.
+impl<E: Endianness, W: EWrite<E>, S: Copy+Serialize<E, W>> Serialize<E, W> for &[S] {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		for elem in self {
+			writer.ewrite(*elem)?;
+		}
+		Ok(())
+	}
+}
+
+/**
+	Writes the elements of the array, without a length prefix. Nested arrays, such as matrices, are written in row-major order.
+
+	Slices of arrays, such as `&[[u8; 4]]`, are written through the slice impl, one element at a time. Even for bytes, they aren't collapsed into a single `write_all`, as that needs specialization, like `&[u8]` itself.
+*/
+impl<E: Endianness, W, S: Serialize<E, W>, const N: usize> Serialize<E, W> for [S; N] {
+	const SIZE: Option<usize> = sum_sizes(&[S::SIZE], N);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		for elem in IntoIterator::into_iter(self) {
+			E::serialize(elem, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Writes the elements of the array, without a length prefix.
+impl<E: Endianness, W: EWrite<E>, S: Copy+Serialize<E, W>, const N: usize> Serialize<E, W> for &[S; N] {
+	const SIZE: Option<usize> = sum_sizes(&[S::SIZE], N);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.ewrite(&self[..])
+	}
+}
+
+/// Writes the entire contents of the Vec.
+impl<E: Endianness, W: EWrite<E>, S: Copy+Serialize<E, W>> Serialize<E, W> for &Vec<S> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.ewrite(self.as_slice())
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the contents of the boxed slice.
+impl<E: Endianness, W: Write, S: Copy+Serialize<E, W>> Serialize<E, W> for &Box<[S]> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		writer.ewrite(&self[..])
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the contents of the slice, whether it's borrowed or owned.
+impl<E: Endianness, W: Write, S: Copy+Serialize<E, W>> Serialize<E, W> for &Cow<'_, [S]> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		writer.ewrite(&self[..])
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &str {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		writer.write_all(self.as_bytes())
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &String {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(self.as_str(), writer)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &Box<str> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(&**self, writer)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &Rc<str> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(&**self, writer)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &Arc<str> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(&**self, writer)
+	}
+}
+
+/// Writes a `u32` length prefix, followed by the UTF-8 bytes of the string.
+impl<E: Endianness, W: Write> Serialize<E, W> for &Cow<'_, str> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(&**self, writer)
+	}
+}
+
+/**
+	Writes the OS string in the same format as `&str`.
+
+	OS strings aren't necessarily valid UTF-8: On Unix they can contain arbitrary bytes, and on Windows unpaired surrogates. Such strings can't be represented portably, so an `InvalidInput` error is returned for them. Use `LossyOsStr` to replace the invalid parts instead.
+*/
+impl<E: Endianness, W: Write> Serialize<E, W> for &OsStr {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let string = self.to_str().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("OS string {:?} is not valid UTF-8", self)))?;
+		Serialize::<E, W>::serialize(string, writer)
+	}
+}
+
+/// Writes the OS string in the same format as `&str`. See `&OsStr` for details.
+impl<E: Endianness, W: Write> Serialize<E, W> for &OsString {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(self.as_os_str(), writer)
+	}
+}
+
+/**
+	Writes an OS string in the same format as `&str`, replacing any parts that aren't valid UTF-8 with U+FFFD REPLACEMENT CHARACTER.
+
+	The replacement is lossy, so reading the string back gives a different string for such OS strings.
+*/
+pub struct LossyOsStr<'a>(pub &'a OsStr);
+
+impl<E: Endianness, W: Write> Serialize<E, W> for LossyOsStr<'_> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		Serialize::<E, W>::serialize(&*self.0.to_string_lossy(), writer)
+	}
+}
+
+/**
+	Writes formatted text in the same format as `&str`, without building an intermediate `String`.
+
+	The arguments are formatted twice, once to compute the length prefix and once to write the text. If the two passes produce a different number of bytes, e.g. because a `Display` impl isn't deterministic, an `InvalidInput` error is returned.
+
+	## Examples
+
+	```
+	use endio::{LEWrite, WriteFmt};
+
+	let mut writer = vec![];
+	writer.ewrite(WriteFmt(format_args!("x={}", 42))).unwrap();
+	assert_eq!(writer, b"\x04\x00\x00\x00x=42");
+	```
+*/
+pub struct WriteFmt<'a>(pub fmt::Arguments<'a>);
+
+struct FmtCounter(usize);
+
+impl fmt::Write for FmtCounter {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0 += s.len();
+		Ok(())
+	}
+}
+
+struct FmtWriter<'a, W> {
+	inner: &'a mut W,
+	written: usize,
+	error: Option<io::Error>,
+}
+
+impl<W: Write> fmt::Write for FmtWriter<'_, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		match self.inner.write_all(s.as_bytes()) {
+			Ok(()) => {
+				self.written += s.len();
+				Ok(())
+			}
+			Err(e) => {
+				self.error = Some(e);
+				Err(fmt::Error)
+			}
+		}
+	}
+}
+
+impl<E: Endianness, W: Write> Serialize<E, W> for WriteFmt<'_> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let mut counter = FmtCounter(0);
+		fmt::write(&mut counter, self.0).map_err(|_| io::Error::other("formatter error"))?;
+		write_len::<E, W>(writer, counter.0)?;
+		let mut fmt_writer = FmtWriter { inner: writer, written: 0, error: None };
+		if fmt::write(&mut fmt_writer, self.0).is_err() {
+			return Err(fmt_writer.error.unwrap_or_else(|| io::Error::other("formatter error")));
+		}
+		if fmt_writer.written != counter.0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "formatted length changed between passes"));
+		}
+		Ok(())
+	}
+}
+
+/**
+	Writes the bytes of a reader with a `u32` length prefix, without loading them into memory.
+
+	The length has to be known up front, since it's written before the data. The data is then copied through a fixed-size buffer. Returns an `InvalidInput` error if the reader yields fewer or more bytes than `len`, in which case the written data is incomplete.
+
+	The format is the same as for `&[u8]` with a length prefix, such as `Box<[u8]>`.
+
+	## Examples
+
+	```
+	use std::io::Cursor;
+	use endio::{LEWrite, StreamBytes};
+
+	let mut writer = vec![];
+	writer.ewrite(StreamBytes { reader: Cursor::new(b"endio"), len: 5 }).unwrap();
+	assert_eq!(writer, b"\x05\x00\x00\x00endio");
+	```
+*/
+pub struct StreamBytes<R> {
+	pub reader: R,
+	pub len: u64,
+}
+
+impl<E: Endianness, W: Write, R: Read> Serialize<E, W> for StreamBytes<R> {
+	fn serialize(mut self, writer: &mut W) -> Res<()> {
+		let len = usize::try_from(self.len).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length does not fit in u32 prefix"))?;
+		write_len::<E, W>(writer, len)?;
+		let copied = io::copy(&mut self.reader.by_ref().take(self.len), writer)?;
+		if copied != self.len {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("reader yielded {} bytes, expected {}", copied, self.len)));
+		}
+		if self.reader.read(&mut [0])? != 0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("reader yielded more than {} bytes", self.len)));
+		}
+		Ok(())
+	}
+}
+
+/// Writes a `u32` length prefix, followed by each key and its value, in the map's iteration order. Use `SortedMap` if the output needs to be reproducible.
+impl<E: Endianness, W: Write, K, V, S> Serialize<E, W> for &HashMap<K, V, S> where for<'a> &'a K: Serialize<E, W>, for<'a> &'a V: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		for (key, value) in self {
+			E::serialize(key, writer)?;
+			E::serialize(value, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Writes a `u32` length prefix, followed by each key and its value, in ascending key order.
+impl<E: Endianness, W: Write, K, V> Serialize<E, W> for &BTreeMap<K, V> where for<'a> &'a K: Serialize<E, W>, for<'a> &'a V: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		write_len::<E, W>(writer, self.len())?;
+		for (key, value) in self {
+			E::serialize(key, writer)?;
+			E::serialize(value, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/**
+	Writes a `HashMap` with its entries sorted by the serialized bytes of their keys.
+
+	`HashMap`'s iteration order differs between runs, so the same map can be written as different bytes. This wrapper writes the entries in a stable order instead, at the cost of serializing all keys into a buffer first. The format is the same as for `&HashMap`, so it's read back as a `HashMap` as usual.
+
+	## Examples
+
+	```
+	use std::collections::HashMap;
+	use endio::{LEWrite, SortedMap};
+
+	let mut map = HashMap::new();
+	map.insert(2u8, true);
+	map.insert(1u8, false);
+
+	let mut writer = vec![];
+	writer.ewrite(SortedMap(&map)).unwrap();
+	assert_eq!(writer, b"\x02\x00\x00\x00\x01\x00\x02\x01");
+	```
+*/
+pub struct SortedMap<'a, K, V, S>(pub &'a HashMap<K, V, S>);
+
+impl<E: Endianness, W: Write, K, V, S> Serialize<E, W> for SortedMap<'_, K, V, S> where for<'a> &'a K: Serialize<E, Vec<u8>>, for<'a> &'a V: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let mut entries = Vec::with_capacity(self.0.len());
+		for (key, value) in self.0 {
+			let mut key_bytes = vec![];
+			E::serialize(key, &mut key_bytes)?;
+			entries.push((key_bytes, value));
+		}
+		entries.sort_by(|a, b| a.0.cmp(&b.0));
+		write_len::<E, W>(writer, entries.len())?;
+		for (key_bytes, value) in entries {
+			writer.write_all(&key_bytes)?;
+			E::serialize(value, writer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Writes the value inside the cell.
+impl<E: Endianness, W, T: Copy+Serialize<E, W>> Serialize<E, W> for &Cell<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.get(), writer)
+	}
+}
+
+/// Borrows the value inside the cell and writes it. Returns an error if the cell is currently mutably borrowed.
+impl<E: Endianness, W, T: ?Sized> Serialize<E, W> for &RefCell<T> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let value = self.try_borrow().map_err(|_| io::Error::other("RefCell is already mutably borrowed"))?;
+		E::serialize(&*value, writer)
+	}
+}
+
+/// Locks the mutex and writes the value inside it. Returns an error if the mutex is poisoned.
+impl<E: Endianness, W, T: ?Sized> Serialize<E, W> for &Mutex<T> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let guard = self.lock().map_err(|_| io::Error::other("mutex is poisoned"))?;
+		E::serialize(&*guard, writer)
+	}
+}
+
+/// Acquires a read lock and writes the value inside the lock. Returns an error if the lock is poisoned.
+impl<E: Endianness, W, T: ?Sized> Serialize<E, W> for &RwLock<T> where for<'a> &'a T: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let guard = self.read().map_err(|_| io::Error::other("lock is poisoned"))?;
+		E::serialize(&*guard, writer)
+	}
+}
+
+/// Loads the value with `SeqCst` ordering and writes it like a `bool`. Use `AtomicLoad` to choose the ordering.
+impl<E: Endianness, W> Serialize<E, W> for &AtomicBool where bool: Serialize<E, W> {
+	const SIZE: Option<usize> = Some(1);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.load(atomic::Ordering::SeqCst), writer)
+	}
+}
+
+/**
+	Writes a snapshot of an atomic, loaded with the given memory ordering, in the same format as the atomic itself.
+
+	Writing an atomic by reference uses `SeqCst`, which is always correct. If the value isn't used to synchronize access to other data, `Relaxed` is enough. As with `load`, `Release` and `AcqRel` panic.
+
+	## Examples
+
+	```
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use endio::{AtomicLoad, LEWrite};
+
+	let flag = AtomicBool::new(true);
+	let mut writer = vec![];
+	writer.ewrite(AtomicLoad(&flag, Ordering::Relaxed)).unwrap();
+	assert_eq!(writer, b"\x01");
+	```
+*/
+pub struct AtomicLoad<'a, A>(pub &'a A, pub atomic::Ordering);
+
+impl<E: Endianness, W> Serialize<E, W> for AtomicLoad<'_, AtomicBool> where bool: Serialize<E, W> {
+	const SIZE: Option<usize> = Some(1);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.0.load(self.1), writer)
+	}
+}
+
+/// Writes a `u8` tag, 0 for `Continue` and 1 for `Break`, followed by the variant's value.
+impl<E: Endianness, W: Write, B: Serialize<E, W>, C: Serialize<E, W>> Serialize<E, W> for ControlFlow<B, C> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		match self {
+			ControlFlow::Continue(value) => {
+				E::serialize(0u8, writer)?;
+				E::serialize(value, writer)
+			}
+			ControlFlow::Break(value) => {
+				E::serialize(1u8, writer)?;
+				E::serialize(value, writer)
+			}
+		}
+	}
+}
+
+/// Can't be called, as `Infallible` has no values. This allows generic types to be written when they're instantiated with a variant that can't occur.
+impl<E: Endianness, W> Serialize<E, W> for Infallible {
+	fn serialize(self, _writer: &mut W) -> Res<()> {
+		match self {}
+	}
+}
+
+/// Can't be called, as `Infallible` has no values.
+impl<E: Endianness, W> Serialize<E, W> for &Infallible {
+	fn serialize(self, _writer: &mut W) -> Res<()> {
+		match *self {}
+	}
+}
+
+/// Writes an `Ordering` as an `i8` of -1 for `Less`, 0 for `Equal` and 1 for `Greater`.
+impl<E: Endianness, W: Write> Serialize<E, W> for Ordering {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self as i8, writer)
+	}
+}
+
+/// Writes `start`, followed by `end`.
+impl<E: Endianness, W, T: Serialize<E, W>> Serialize<E, W> for Range<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.start, writer)?;
+		E::serialize(self.end, writer)
+	}
+}
+
+/// Writes `start`.
+impl<E: Endianness, W, T: Serialize<E, W>> Serialize<E, W> for RangeFrom<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.start, writer)
+	}
+}
+
+/// Writes `end`.
+impl<E: Endianness, W, T: Serialize<E, W>> Serialize<E, W> for RangeTo<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self.end, writer)
+	}
+}
+
+/// Writes nothing.
+impl<E: Endianness, W> Serialize<E, W> for RangeFull {
+	fn serialize(self, _writer: &mut W) -> Res<()> {
+		Ok(())
+	}
+}
+
+/// Writes a bool by writing a byte.
+impl<E: Endianness, W: Write> Serialize<E, W> for bool {
+	const SIZE: Option<usize> = Some(1);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.write_all(&(self as u8).to_ne_bytes())
+	}
+}
+
+impl<E: Endianness, W: Write> Serialize<E, W> for u8 {
+	const SIZE: Option<usize> = Some(1);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.write_all(&self.to_ne_bytes())
+	}
+}
+
+impl<E: Endianness, W: Write> Serialize<E, W> for i8 {
+	const SIZE: Option<usize> = Some(1);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.write_all(&self.to_ne_bytes())
+	}
+}
+
+macro_rules! impl_int {
+	($t:ident) => {
+		impl<E: Endianness, W: Write> Serialize<E, W> for $t {
+			const SIZE: Option<usize> = Some(size_of::<$t>());
+
+			fn serialize_be(self, writer: &mut W) -> Res<()> {
+				writer.write_all(&self.to_be_bytes())
+			}
+
+			fn serialize_le(self, writer: &mut W) -> Res<()> {
+				writer.write_all(&self.to_le_bytes())
+			}
+		}
+
+		#[cfg(test)]
+		mod $t {
+			use std::mem::size_of;
+
+			#[test]
+			fn test() {
+				let integer: u128 = 0xbaadf00dbaadf00dbaadf00dbaadf00d;
+				let bytes = b"\x0d\xf0\xad\xba\x0d\xf0\xad\xba\x0d\xf0\xad\xba\x0d\xf0\xad\xba";
+
+				{
+					use crate::BEWrite;
+					let mut writer = vec![];
+					writer.ewrite((integer as $t).to_be()).unwrap();
+					assert_eq!(&writer[..], &bytes[..size_of::<$t>()]);
+				}
+				{
+					use crate::LEWrite;
+					let mut writer = vec![];
+					writer.ewrite((integer as $t).to_le()).unwrap();
+					assert_eq!(&writer[..], &bytes[..size_of::<$t>()]);
+				}
+			}
+		}
+	}
+}
+
+impl_int!(u16);
+impl_int!(u32);
+impl_int!(u64);
+impl_int!(u128);
+impl_int!(i16);
+impl_int!(i32);
+impl_int!(i64);
+impl_int!(i128);
+
+impl<E: Endianness, W: EWrite<E>> Serialize<E, W> for f32 where u32: Serialize<E, W> {
+	const SIZE: Option<usize> = Some(4);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.ewrite(self.to_bits())
+	}
+}
+
+impl<E: Endianness, W: EWrite<E>> Serialize<E, W> for f64 where u64: Serialize<E, W> {
+	const SIZE: Option<usize> = Some(8);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		writer.ewrite(self.to_bits())
+	}
+}
+
+/// Writes a char as its `u32` Unicode scalar value.
+impl<E: Endianness, W> Serialize<E, W> for char where u32: Serialize<E, W> {
+	const SIZE: Option<usize> = Some(4);
+
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(self as u32, writer)
+	}
+}
+
+macro_rules! impl_nonzero {
+	($t:ident, $int:ident) => {
+		impl<E: Endianness, W> Serialize<E, W> for $t where $int: Serialize<E, W> {
+			const SIZE: Option<usize> = Some(size_of::<$int>());
+
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				E::serialize(self.get(), writer)
+			}
+		}
+	}
+}
+
+impl_nonzero!(NonZeroU8, u8);
+impl_nonzero!(NonZeroU16, u16);
+impl_nonzero!(NonZeroU32, u32);
+impl_nonzero!(NonZeroU64, u64);
+impl_nonzero!(NonZeroU128, u128);
+impl_nonzero!(NonZeroI8, i8);
+impl_nonzero!(NonZeroI16, i16);
+impl_nonzero!(NonZeroI32, i32);
+impl_nonzero!(NonZeroI64, i64);
+impl_nonzero!(NonZeroI128, i128);
+
+macro_rules! impl_tuple {
+	($($name:ident)+) => {
+		/// Writes the elements in order.
+		impl<E: Endianness, W, $($name: Serialize<E, W>),+> Serialize<E, W> for ($($name,)+) {
+			const SIZE: Option<usize> = sum_sizes(&[$($name::SIZE),+], 1);
+
+			#[allow(non_snake_case)]
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				let ($($name,)+) = self;
+				$(E::serialize($name, writer)?;)+
+				Ok(())
+			}
+		}
+	}
+}
+
+impl_tuple!(A);
+impl_tuple!(A B);
+impl_tuple!(A B C);
+impl_tuple!(A B C D);
+impl_tuple!(A B C D F);
+impl_tuple!(A B C D F G);
+impl_tuple!(A B C D F G H);
+impl_tuple!(A B C D F G H I);
+impl_tuple!(A B C D F G H I J);
+impl_tuple!(A B C D F G H I J K);
+impl_tuple!(A B C D F G H I J K L);
+impl_tuple!(A B C D F G H I J K L M);
+
+// Allows primitives to be written by reference, and through the impl above, by mutable reference.
+macro_rules! impl_ref {
+	($t:ident) => {
+		impl<E: Endianness, W> Serialize<E, W> for &$t where $t: Serialize<E, W> {
+			const SIZE: Option<usize> = <$t as Serialize<E, W>>::SIZE;
+
+			fn serialize_be(self, writer: &mut W) -> Res<()> {
+				Serialize::<E, W>::serialize_be(*self, writer)
+			}
+
+			fn serialize_le(self, writer: &mut W) -> Res<()> {
+				Serialize::<E, W>::serialize_le(*self, writer)
+			}
+		}
+	}
+}
+
+impl_ref!(bool);
+impl_ref!(u8);
+impl_ref!(i8);
+impl_ref!(u16);
+impl_ref!(u32);
+impl_ref!(u64);
+impl_ref!(u128);
+impl_ref!(i16);
+impl_ref!(i32);
+impl_ref!(i64);
+impl_ref!(i128);
+impl_ref!(f32);
+impl_ref!(f64);
+impl_ref!(char);
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision, clippy::legacy_numeric_constants)]
+mod tests {
+	use std::io::Result as Res;
+
+	#[test]
+	fn write_slice() {
+		let data = b"\xba\xad\xba\xad";
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.ewrite(&[0xadbau16, 0xadbau16][..]).unwrap();
+		assert_eq!(writer, data);
+	}
+
+	#[test]
+	fn write_array() {
+		let data = b"\xba\xad\xf0\x0d";
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite([0xbaadu16, 0xf00d]).unwrap();
+			assert_eq!(writer, data);
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			let val: &[u16; 2] = &[0xadba, 0x0df0];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, data);
+		}
+	}
+
+	#[test]
+	fn write_slice_of_arrays() {
+		use crate::LEWrite;
+		let pixels: &[[u8; 4]] = &[[0xff, 0x00, 0x00, 0xff], [0x00, 0xff, 0x00, 0x80], [0x00, 0x00, 0xff, 0x00]];
+		let mut writer = vec![];
+		writer.ewrite(pixels).unwrap();
+		assert_eq!(writer, b"\xff\x00\x00\xff\x00\xff\x00\x80\x00\x00\xff\x00");
+	}
+
+	#[test]
+	fn write_vec() {
+		let data = b"\xba\xad\xba\xad";
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.ewrite(&vec![0xadbau16, 0xadbau16]).unwrap();
+		assert_eq!(writer, data);
+	}
+
+	#[test]
+	fn write_boxed_slice() {
+		let val: Box<[u32]> = Box::new([0xbaadf00d, 0x2a]);
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(&val).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x02\xba\xad\xf0\x0d\x00\x00\x00\x2a");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(&val).unwrap();
+			assert_eq!(writer, b"\x02\x00\x00\x00\x0d\xf0\xad\xba\x2a\x00\x00\x00");
+		}
+	}
+
+	#[test]
+	fn write_boxed_slice_empty() {
+		use crate::LEWrite;
+		let val: Box<[u32]> = Box::new([]);
+		let mut writer = vec![];
+		writer.ewrite(&val).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+	}
+
+	#[test]
+	fn write_str() {
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite("endio").unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x05endio");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(&String::from("endio")).unwrap();
+			assert_eq!(writer, b"\x05\x00\x00\x00endio");
+		}
+	}
+
+	#[test]
+	fn write_cow_str() {
+		use std::borrow::Cow;
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.ewrite(&Cow::Borrowed("endio")).unwrap();
+		writer.ewrite(&Cow::<str>::Owned("endio".into())).unwrap();
+		assert_eq!(writer, b"\x05\x00\x00\x00endio\x05\x00\x00\x00endio");
+	}
+
+	#[test]
+	fn write_cow_slice() {
+		use std::borrow::Cow;
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.ewrite(&Cow::Borrowed(&[0xbaadu32, 0xf00d][..])).unwrap();
+		writer.ewrite(&Cow::<[u32]>::Owned(vec![0xbaad, 0xf00d])).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x02\x00\x00\xba\xad\x00\x00\xf0\x0d\x00\x00\x00\x02\x00\x00\xba\xad\x00\x00\xf0\x0d");
 	}
-	```
-*/
-pub trait Serialize<E: Endianness, W>: Sized {
-	/// Serializes the type by writing to the writer using Big-endian.
-	/// Implement ONLY this method if your code for both endianness is the same.
-	fn serialize(self, _writer: &mut W) -> Res<()> {
-		unreachable!();
+
+	#[test]
+	fn write_shared_str() {
+		use std::rc::Rc;
+		use std::sync::Arc;
+		use crate::BEWrite;
+		let mut expected = vec![];
+		expected.ewrite(&String::from("endio")).unwrap();
+		let mut writer = vec![];
+		writer.ewrite(&Box::<str>::from("endio")).unwrap();
+		assert_eq!(writer, expected);
+		let mut writer = vec![];
+		writer.ewrite(&Rc::<str>::from("endio")).unwrap();
+		assert_eq!(writer, expected);
+		let mut writer = vec![];
+		writer.ewrite(&Arc::<str>::from("endio")).unwrap();
+		assert_eq!(writer, expected);
 	}
 
-	/// Serializes the type by writing to the writer using Big-endian.
-	fn serialize_be(self, writer: &mut W) -> Res<()> {
-		self.serialize(writer)
+	#[test]
+	fn write_os_string() {
+		use std::ffi::OsString;
+		use crate::{LEWrite, LossyOsStr};
+		let val = OsString::from("endio");
+		let mut writer = vec![];
+		writer.ewrite(&val).unwrap();
+		writer.ewrite(LossyOsStr(&val)).unwrap();
+		assert_eq!(writer, b"\x05\x00\x00\x00endio\x05\x00\x00\x00endio");
 	}
 
-	/// Serializes the type by writing to the writer using Little-endian.
-	fn serialize_le(self, writer: &mut W) -> Res<()> {
-		self.serialize(writer)
+	#[cfg(unix)]
+	#[test]
+	fn write_os_string_non_utf8() {
+		use std::ffi::OsStr;
+		use std::io;
+		use std::os::unix::ffi::OsStrExt;
+		use crate::{BEWrite, LossyOsStr};
+		let val = OsStr::from_bytes(b"end\xffio");
+		let mut writer = vec![];
+		let err = writer.ewrite(val).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(writer.is_empty());
+		writer.ewrite(LossyOsStr(val)).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x08end\xef\xbf\xbdio");
 	}
-}
 
-// todo[specialization]: specialize for &[u8] (std::io::Write::write_all)
-/// Writes the entire contents of the byte slice.
-impl<E: Endianness, W: EWrite<E>, S: Copy+Serialize<E, W>> Serialize<E, W> for &[S] {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		for elem in self {
-			writer.ewrite(*elem)?;
+	#[test]
+	fn write_matrix() {
+		use crate::{BERead, BEWrite};
+		let mut matrix = [[0f32; 4]; 4];
+		for (i, row) in matrix.iter_mut().enumerate() {
+			for (j, elem) in row.iter_mut().enumerate() {
+				*elem = (i * 4 + j) as f32;
+			}
 		}
-		Ok(())
+		let mut writer = vec![];
+		writer.ewrite(matrix).unwrap();
+		assert_eq!(writer.len(), 64);
+		let mut reader = &writer[..];
+		let flat: [f32; 16] = reader.eread().unwrap();
+		for (i, elem) in flat.iter().enumerate() {
+			assert_eq!(*elem, i as f32);
+		}
+		let mut reader = &writer[..];
+		let val: [[f32; 4]; 4] = reader.eread().unwrap();
+		assert_eq!(val, matrix);
 	}
-}
 
-/// Writes the entire contents of the Vec.
-impl<E: Endianness, W: EWrite<E>, S: Copy+Serialize<E, W>> Serialize<E, W> for &Vec<S> {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.ewrite(self.as_slice())
+	#[test]
+	fn write_fmt() {
+		use crate::{BERead, BEWrite, WriteFmt};
+		let mut writer = vec![];
+		writer.ewrite(WriteFmt(format_args!("x={}", 42))).unwrap();
+		assert_eq!(writer, b"\x00\x00\x00\x04x=42");
+		let mut reader = &writer[..];
+		let val: String = reader.eread().unwrap();
+		assert_eq!(val, "x=42");
 	}
-}
 
-/// Writes a bool by writing a byte.
-impl<E: Endianness, W: Write> Serialize<E, W> for bool {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.write_all(&(self as u8).to_ne_bytes())
+	#[test]
+	fn write_stream_bytes() {
+		use std::io::Cursor;
+		use crate::{LERead, LEWrite, StreamBytes};
+		let data: Vec<u8> = (0..1 << 20).map(|i| i as u8).collect();
+		let mut writer = vec![];
+		writer.ewrite(StreamBytes { reader: Cursor::new(&data), len: data.len() as u64 }).unwrap();
+		assert_eq!(writer.len(), 4 + data.len());
+		let mut reader = &writer[..];
+		let val: Box<[u8]> = reader.eread().unwrap();
+		assert_eq!(*val, *data);
 	}
-}
 
-impl<E: Endianness, W: Write> Serialize<E, W> for u8 {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.write_all(&self.to_ne_bytes())
+	#[test]
+	fn write_stream_bytes_wrong_len() {
+		use std::io;
+		use crate::{BEWrite, StreamBytes};
+		let mut writer = vec![];
+		let err = writer.ewrite(StreamBytes { reader: &b"endio"[..], len: 6 }).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "reader yielded 5 bytes, expected 6");
+		let err = writer.ewrite(StreamBytes { reader: &b"endio"[..], len: 4 }).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "reader yielded more than 4 bytes");
 	}
-}
 
-impl<E: Endianness, W: Write> Serialize<E, W> for i8 {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.write_all(&self.to_ne_bytes())
+	#[test]
+	fn write_hash_map() {
+		use std::collections::HashMap;
+		let mut map = HashMap::new();
+		map.insert(0xbaadu16, String::from("endio"));
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(&map).unwrap();
+			assert_eq!(writer, b"\x00\x00\x00\x01\xba\xad\x00\x00\x00\x05endio");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(&map).unwrap();
+			assert_eq!(writer, b"\x01\x00\x00\x00\xad\xba\x05\x00\x00\x00endio");
+		}
 	}
-}
 
-macro_rules! impl_int {
-	($t:ident) => {
-		impl<E: Endianness, W: Write> Serialize<E, W> for $t {
-			fn serialize_be(self, writer: &mut W) -> Res<()> {
-				writer.write_all(&self.to_be_bytes())
-			}
+	#[test]
+	fn write_btree_map_borrowed() {
+		use std::collections::BTreeMap;
+		use crate::LEWrite;
+		let mut map = BTreeMap::new();
+		map.insert(2u8, String::from("io"));
+		map.insert(1u8, String::from("end"));
+		let borrowed = &map;
+		let mut writer = vec![];
+		writer.ewrite(borrowed).unwrap();
+		assert_eq!(writer, b"\x02\x00\x00\x00\x01\x03\x00\x00\x00end\x02\x02\x00\x00\x00io");
+		assert_eq!(map.len(), 2);
+	}
 
-			fn serialize_le(self, writer: &mut W) -> Res<()> {
-				writer.write_all(&self.to_le_bytes())
-			}
+	#[test]
+	fn write_sorted_map_reproducible() {
+		use std::collections::HashMap;
+		use crate::{LEWrite, SortedMap};
+		let mut first = HashMap::new();
+		let mut second = HashMap::new();
+		for i in 0..100u32 {
+			first.insert(i, i as u8);
+			second.insert(99 - i, (99 - i) as u8);
 		}
+		let mut first_writer = vec![];
+		first_writer.ewrite(SortedMap(&first)).unwrap();
+		let mut second_writer = vec![];
+		second_writer.ewrite(SortedMap(&second)).unwrap();
+		let mut again = vec![];
+		again.ewrite(SortedMap(&first)).unwrap();
+		assert_eq!(first_writer, second_writer);
+		assert_eq!(first_writer, again);
+		assert_eq!(first_writer.len(), 4 + 100 * 5);
+	}
 
-		#[cfg(test)]
-		mod $t {
-			use std::mem::size_of;
+	#[test]
+	fn write_cells() {
+		use std::cell::{Cell, RefCell};
+		use crate::{BEWrite, LEWrite};
+		let cell = Cell::new(0xbaadu16);
+		let mut writer = vec![];
+		BEWrite::ewrite(&mut writer, &cell).unwrap();
+		assert_eq!(writer, b"\xba\xad");
+		let ref_cell = RefCell::new(0xbaadu16);
+		let mut writer = vec![];
+		LEWrite::ewrite(&mut writer, &ref_cell).unwrap();
+		assert_eq!(writer, b"\xad\xba");
+		let _borrow = ref_cell.borrow_mut();
+		let err = LEWrite::ewrite(&mut writer, &ref_cell).unwrap_err();
+		assert_eq!(err.to_string(), "RefCell is already mutably borrowed");
+	}
 
-			#[test]
-			fn test() {
-				let integer: u128 = 0xbaadf00dbaadf00dbaadf00dbaadf00d;
-				let bytes = b"\x0d\xf0\xad\xba\x0d\xf0\xad\xba\x0d\xf0\xad\xba\x0d\xf0\xad\xba";
+	#[test]
+	fn write_mutex() {
+		use std::sync::{Arc, Mutex, RwLock};
+		use crate::{BEWrite, LEWrite};
+		let mutex = Mutex::new(0xbaadf00du32);
+		let mut writer = vec![];
+		BEWrite::ewrite(&mut writer, &mutex).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d");
+		let lock = RwLock::new(0xbaadu16);
+		let mut writer = vec![];
+		LEWrite::ewrite(&mut writer, &lock).unwrap();
+		assert_eq!(writer, b"\xad\xba");
 
-				{
-					use crate::BEWrite;
-					let mut writer = vec![];
-					writer.ewrite((integer as $t).to_be()).unwrap();
-					assert_eq!(&writer[..], &bytes[..size_of::<$t>()]);
-				}
-				{
-					use crate::LEWrite;
-					let mut writer = vec![];
-					writer.ewrite((integer as $t).to_le()).unwrap();
-					assert_eq!(&writer[..], &bytes[..size_of::<$t>()]);
-				}
-			}
-		}
+		let mutex = Arc::new(Mutex::new(42u8));
+		let poisoner = Arc::clone(&mutex);
+		std::thread::spawn(move || {
+			let _guard = poisoner.lock().unwrap();
+			panic!("poisoning the mutex");
+		}).join().unwrap_err();
+		let err = BEWrite::ewrite(&mut writer, &*mutex).unwrap_err();
+		assert_eq!(err.to_string(), "mutex is poisoned");
 	}
-}
 
-impl_int!(u16);
-impl_int!(u32);
-impl_int!(u64);
-impl_int!(u128);
-impl_int!(i16);
-impl_int!(i32);
-impl_int!(i64);
-impl_int!(i128);
+	#[test]
+	fn write_atomic_bool() {
+		use std::sync::atomic::{AtomicBool, Ordering};
+		use crate::{AtomicLoad, BERead, BEWrite};
+		let flag = AtomicBool::new(true);
+		let mut writer = vec![];
+		writer.ewrite(&flag).unwrap();
+		flag.store(false, Ordering::SeqCst);
+		writer.ewrite(AtomicLoad(&flag, Ordering::Acquire)).unwrap();
+		assert_eq!(writer, b"\x01\x00");
+		let mut reader = &writer[..];
+		let read: AtomicBool = reader.eread().unwrap();
+		assert!(read.load(Ordering::SeqCst));
+		let read: AtomicBool = reader.eread().unwrap();
+		assert!(!read.into_inner());
+	}
 
-impl<E: Endianness, W: EWrite<E>> Serialize<E, W> for f32 where u32: Serialize<E, W> {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.ewrite(self.to_bits())
+	#[test]
+	fn write_ranges() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(0xbaadu16..0xf00d).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d");
+		let mut writer = vec![];
+		writer.ewrite(0xbaadu16..).unwrap();
+		writer.ewrite(..0xf00du16).unwrap();
+		writer.ewrite(..).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d");
+		let mut reader = &writer[..];
+		let from: std::ops::RangeFrom<u16> = reader.eread().unwrap();
+		let to: std::ops::RangeTo<u16> = reader.eread().unwrap();
+		assert_eq!((from, to), (0xbaad.., ..0xf00d));
+		assert!(reader.is_empty());
 	}
-}
 
-impl<E: Endianness, W: EWrite<E>> Serialize<E, W> for f64 where u64: Serialize<E, W> {
-	fn serialize(self, writer: &mut W) -> Res<()> {
-		writer.ewrite(self.to_bits())
+	#[test]
+	fn write_infallible_variant() {
+		use std::convert::Infallible;
+		use std::ops::ControlFlow;
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.ewrite(ControlFlow::<Infallible, u16>::Continue(0xbaad)).unwrap();
+		writer.ewrite(ControlFlow::<&Infallible, &u8>::Continue(&42)).unwrap();
+		assert_eq!(writer, b"\x00\xba\xad\x00\x2a");
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use std::io::Result as Res;
+	#[test]
+	fn write_control_flow() {
+		use std::ops::ControlFlow;
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(ControlFlow::<u8, u16>::Continue(0xbaad)).unwrap();
+			writer.ewrite(ControlFlow::<u16, u8>::Break(0xbaad)).unwrap();
+			assert_eq!(writer, b"\x00\xba\xad\x01\xba\xad");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(ControlFlow::<u8, u16>::Continue(0xbaad)).unwrap();
+			writer.ewrite(ControlFlow::<u16, u8>::Break(0xbaad)).unwrap();
+			assert_eq!(writer, b"\x00\xad\xba\x01\xad\xba");
+		}
+	}
 
 	#[test]
-	fn write_slice() {
-		let data = b"\xba\xad\xba\xad";
-		use crate::LEWrite;
-		let mut writer = vec![];
-		writer.ewrite(&[0xadbau16, 0xadbau16][..]).unwrap();
-		assert_eq!(writer, data);
+	fn write_tuple() {
+		let val = (0x2au8, 0xbaadu16, true);
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x2a\xba\xad\x01");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x2a\xad\xba\x01");
+		}
 	}
 
 	#[test]
-	fn write_vec() {
-		let data = b"\xba\xad\xba\xad";
+	fn write_ordering() {
+		use std::cmp::Ordering;
 		use crate::LEWrite;
 		let mut writer = vec![];
-		writer.ewrite(&vec![0xadbau16, 0xadbau16]).unwrap();
-		assert_eq!(writer, data);
+		writer.ewrite(Ordering::Less).unwrap();
+		writer.ewrite(Ordering::Equal).unwrap();
+		writer.ewrite(Ordering::Greater).unwrap();
+		assert_eq!(writer, b"\xff\x00\x01");
 	}
 
 	#[test]
@@ -273,6 +1258,41 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn write_char() {
+		let val = '\u{1f980}';
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x00\x01\xf9\x80");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\x80\xf9\x01\x00");
+		}
+	}
+
+	#[test]
+	fn write_nonzero() {
+		use std::num::NonZeroU16;
+		let val = NonZeroU16::new(0xbaad).unwrap();
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\xba\xad");
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, b"\xad\xba");
+		}
+	}
+
 	#[test]
 	fn write_i8() {
 		let data = b"\x80";
@@ -343,6 +1363,81 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn write_ref() {
+		let data = b"\xba\xad\xf0\x0d";
+		let val: &u32 = &0xbaadf00d;
+		{
+			use crate::BEWrite;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, data);
+		}
+		{
+			use crate::LEWrite;
+			let mut writer = vec![];
+			writer.write_be(val).unwrap();
+			assert_eq!(writer, data);
+		}
+	}
+
+	#[test]
+	fn write_mut_ref() {
+		let data = b"\xba\xad\xf0\x0d";
+		let mut int = 0xbaadf00du32;
+		{
+			use crate::BEWrite;
+			let val: &mut u32 = &mut int;
+			let mut writer = vec![];
+			writer.ewrite(val).unwrap();
+			assert_eq!(writer, data);
+		}
+		{
+			use crate::LEWrite;
+			let val: &mut u32 = &mut int;
+			let mut writer = vec![];
+			writer.write_be(val).unwrap();
+			assert_eq!(writer, data);
+		}
+	}
+
+	#[test]
+	fn write_mut_ref_generic() {
+		fn write_all<E: crate::Endianness, W: crate::EWrite<E>, T>(writer: &mut W, values: &mut [T]) -> Res<()> where for<'a> &'a mut T: crate::Serialize<E, W> {
+			for value in values {
+				writer.ewrite(value)?;
+			}
+			Ok(())
+		}
+
+		use crate::LEWrite;
+		let mut writer = vec![];
+		write_all::<crate::LittleEndian, _, u16>(&mut writer, &mut [0xbaadu16, 0xf00d]).unwrap();
+		assert_eq!(writer, b"\xad\xba\x0d\xf0");
+		let mut writer = vec![];
+		writer.ewrite(&mut vec![0xbaadu16]).unwrap();
+		assert_eq!(writer, b"\xad\xba");
+	}
+
+	#[test]
+	fn write_f32_forced() {
+		let data = b"\x44\x20\xa7\x44\x44\x20\xa7\x44";
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.ewrite(642.6135f32).unwrap();
+		writer.write_le(1337.0083f32).unwrap();
+		assert_eq!(writer, data);
+	}
+
+	#[test]
+	fn write_f64_forced() {
+		let data = b"\x40\x94\x7a\x14\xae\xe5\x94\x40";
+		use crate::LEWrite;
+		let mut writer = vec![];
+		writer.write_be(1310.5201984283194f64).unwrap();
+		assert_eq!(writer, data);
+	}
+
 	#[test]
 	fn write_struct_forced() {
 		struct Test {
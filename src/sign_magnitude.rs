@@ -0,0 +1,110 @@
+use std::io;
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+
+/**
+	A signed integer read/written in sign-magnitude representation instead of two's complement: the top bit is the sign, the other bits are the absolute value.
+
+	Sign-magnitude has both a positive and a negative zero, both are read as 0. In turn it can't represent the type's `MIN`, so writing `MIN` returns an `InvalidInput` error.
+
+	## Examples
+
+	```
+	use endio::{BERead, BEWrite, SignMagnitude};
+
+	let mut writer = vec![];
+	writer.ewrite(SignMagnitude(-2i16)).unwrap();
+	assert_eq!(writer, b"\x80\x02");
+
+	let mut reader = &writer[..];
+	let SignMagnitude(value): SignMagnitude<i16> = reader.eread().unwrap();
+	assert_eq!(value, -2);
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignMagnitude<T>(pub T);
+
+macro_rules! impl_sign_magnitude {
+	($t:ident, $u:ident) => {
+		impl<E: Endianness, W> Serialize<E, W> for SignMagnitude<$t> where $u: Serialize<E, W> {
+			fn serialize(self, writer: &mut W) -> Res<()> {
+				if self.0 == $t::MIN {
+					return Err(io::Error::new(io::ErrorKind::InvalidInput, concat!(stringify!($t), "::MIN can't be represented in sign-magnitude")));
+				}
+				let magnitude = self.0.unsigned_abs();
+				let sign = if self.0 < 0 { 1 << ($u::BITS - 1) } else { 0 };
+				E::serialize(sign | magnitude, writer)
+			}
+		}
+
+		impl<E: Endianness, R> Deserialize<E, R> for SignMagnitude<$t> where $u: Deserialize<E, R> {
+			fn deserialize(reader: &mut R) -> Res<Self> {
+				let bits: $u = E::deserialize(reader)?;
+				let magnitude = (bits & $t::MAX as $u) as $t;
+				Ok(SignMagnitude(if bits >> ($u::BITS - 1) == 1 { -magnitude } else { magnitude }))
+			}
+		}
+	}
+}
+
+impl_sign_magnitude!(i8, u8);
+impl_sign_magnitude!(i16, u16);
+impl_sign_magnitude!(i32, u32);
+impl_sign_magnitude!(i64, u64);
+impl_sign_magnitude!(i128, u128);
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+
+	use super::SignMagnitude;
+
+	#[test]
+	fn negative_zero() {
+		use crate::LERead;
+		let mut reader = &b"\x00\x80\x00\x00"[..];
+		let val: SignMagnitude<i16> = reader.eread().unwrap();
+		assert_eq!(val.0, 0);
+		let val: SignMagnitude<i16> = reader.eread().unwrap();
+		assert_eq!(val.0, 0);
+	}
+
+	#[test]
+	fn minus_one() {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(SignMagnitude(-1i32)).unwrap();
+		assert_eq!(writer, b"\x80\x00\x00\x01");
+		let mut reader = &writer[..];
+		let val: SignMagnitude<i32> = reader.eread().unwrap();
+		assert_eq!(val.0, -1);
+	}
+
+	#[test]
+	fn limits() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(SignMagnitude(i8::MAX)).unwrap();
+		writer.ewrite(SignMagnitude(-i8::MAX)).unwrap();
+		writer.ewrite(SignMagnitude(i64::MAX)).unwrap();
+		writer.ewrite(SignMagnitude(-i64::MAX)).unwrap();
+		assert_eq!(writer[..2], [0x7f, 0xff]);
+		assert_eq!(writer[2..], *b"\xff\xff\xff\xff\xff\xff\xff\x7f\xff\xff\xff\xff\xff\xff\xff\xff");
+		let mut reader = &writer[..];
+		assert_eq!(reader.eread::<SignMagnitude<i8>>().unwrap().0, i8::MAX);
+		assert_eq!(reader.eread::<SignMagnitude<i8>>().unwrap().0, -i8::MAX);
+		assert_eq!(reader.eread::<SignMagnitude<i64>>().unwrap().0, i64::MAX);
+		assert_eq!(reader.eread::<SignMagnitude<i64>>().unwrap().0, -i64::MAX);
+	}
+
+	#[test]
+	fn min_unrepresentable() {
+		use crate::LEWrite;
+		let mut writer = vec![];
+		let err = writer.ewrite(SignMagnitude(i8::MIN)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(err.to_string(), "i8::MIN can't be represented in sign-magnitude");
+		assert!(writer.is_empty());
+	}
+}
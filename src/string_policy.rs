@@ -0,0 +1,220 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+use std::marker::PhantomData;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{invalid_data, read_bytes, read_len};
+
+/**
+	How to handle invalid UTF-8 when reading a string with `read_string` or as a `PolicyString`.
+
+	Make your parsing code generic over the policy to choose it once for the whole parser.
+*/
+pub trait StringPolicy {
+	/// Converts the bytes read to a string.
+	fn decode(bytes: Vec<u8>) -> Res<String>;
+}
+
+/// Returns an `InvalidData` error for invalid UTF-8. This is what reading a `String` does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Strict;
+
+/// Replaces invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lossy;
+
+impl StringPolicy for Strict {
+	fn decode(bytes: Vec<u8>) -> Res<String> {
+		String::from_utf8(bytes).map_err(|e| invalid_data(format!("string is not valid UTF-8: {}", e)))
+	}
+}
+
+impl StringPolicy for Lossy {
+	fn decode(bytes: Vec<u8>) -> Res<String> {
+		match String::from_utf8(bytes) {
+			Ok(string) => Ok(string),
+			Err(e) => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+		}
+	}
+}
+
+/// Reads a string in the same format as `String`, decoding it with the policy.
+pub(crate) fn read_string<E: Endianness, R: Read, P: StringPolicy>(reader: &mut R) -> Res<String> {
+	let len = read_len::<E, R>(reader)?;
+	P::decode(read_bytes(reader, len)?)
+}
+
+/**
+	A string read with the policy `P` instead of `Strict`.
+
+	`S` is the string type whose format is used, and the other wrappers can hold a `PolicyString` in place of their `String`: `NullableLengthPrefixed<PolicyString<P>>`, `CompactLenPrefixed<PolicyString<P>>` and `BomAwareString<PolicyString<P>>` are read like the `String` versions, as is `PolicyString<P, ArrayString<N>>` with the `arrayvec` feature. Writing is the same as writing `S`.
+
+	## Examples
+
+	```
+	use endio::{LERead, Lossy, PolicyString};
+
+	let mut reader = &b"\x06\x00\x00\x00end\xffio"[..];
+	let string: PolicyString<Lossy> = reader.eread().unwrap();
+	assert_eq!(string.0, "end\u{fffd}io");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PolicyString<P, S = String>(pub S, pub PhantomData<P>);
+
+impl<P, S> PolicyString<P, S> {
+	pub fn new(string: S) -> Self {
+		PolicyString(string, PhantomData)
+	}
+
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+impl<P, S: AsRef<str>> AsRef<str> for PolicyString<P, S> {
+	fn as_ref(&self) -> &str {
+		self.0.as_ref()
+	}
+}
+
+impl<P, S: AsRef<[u8]>> AsRef<[u8]> for PolicyString<P, S> {
+	fn as_ref(&self) -> &[u8] {
+		self.0.as_ref()
+	}
+}
+
+impl<'a, E: Endianness, W: Write, P, S> Serialize<E, W> for &'a PolicyString<P, S> where &'a S: Serialize<E, W> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		E::serialize(&self.0, writer)
+	}
+}
+
+impl<E: Endianness, R: Read, P: StringPolicy> Deserialize<E, R> for PolicyString<P> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		read_string::<E, R, P>(reader).map(PolicyString::new)
+	}
+}
+
+const BOM: char = '\u{feff}';
+
+/**
+	A string in the same format as `String`, where the string data may start with a UTF-8 byte order mark (`EF BB BF`).
+
+	Reading strips a leading BOM after the length prefix is decoded, so the string contains only the text. Writing never emits one: A leading BOM in the string is left out, and the length prefix counts only the bytes written.
+
+	Any `T: AsRef<str>` can be written. Reading is implemented for `String`, and for `PolicyString<P>` to decode with another policy.
+
+	## Examples
+
+	```
+	use endio::{BomAwareString, LERead, LEWrite};
+
+	let mut reader = &b"\x08\x00\x00\x00\xef\xbb\xbfendio"[..];
+	let value: BomAwareString<String> = reader.eread().unwrap();
+	assert_eq!(value.0, "endio");
+
+	let mut writer = vec![];
+	writer.ewrite(BomAwareString("\u{feff}endio")).unwrap();
+	assert_eq!(writer, b"\x05\x00\x00\x00endio");
+	```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BomAwareString<T>(pub T);
+
+impl<E: Endianness, W: Write, T: AsRef<str>> Serialize<E, W> for BomAwareString<T> {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		let string = self.0.as_ref();
+		E::serialize(string.strip_prefix(BOM).unwrap_or(string), writer)
+	}
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for BomAwareString<String> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let BomAwareString(string) = E::deserialize::<R, BomAwareString<PolicyString<Strict>>>(reader)?;
+		Ok(BomAwareString(string.0))
+	}
+}
+
+impl<E: Endianness, R: Read, P: StringPolicy> Deserialize<E, R> for BomAwareString<PolicyString<P>> {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		let mut string = read_string::<E, R, P>(reader)?;
+		if string.starts_with(BOM) {
+			string.drain(..BOM.len_utf8());
+		}
+		Ok(BomAwareString(PolicyString::new(string)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{BomAwareString, Lossy, PolicyString, Strict};
+
+	const INVALID: &[u8] = b"\x06\x00\x00\x00end\xffio";
+
+	#[test]
+	fn strict() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = INVALID;
+		let err = reader.read_string::<Strict>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn lossy() {
+		use crate::LERead;
+		let mut reader = INVALID;
+		assert_eq!(reader.read_string::<Lossy>().unwrap(), "end\u{fffd}io");
+	}
+
+	#[test]
+	fn valid() {
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x05endio\x00\x00\x00\x05endio"[..];
+		assert_eq!(reader.read_string::<Strict>().unwrap(), "endio");
+		assert_eq!(reader.read_string::<Lossy>().unwrap(), "endio");
+	}
+
+	#[test]
+	fn bom_stripped() {
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x08\xef\xbb\xbfendio"[..];
+		let val: BomAwareString<String> = reader.eread().unwrap();
+		assert_eq!(val.0, "endio");
+	}
+
+	#[test]
+	fn bom_plain() {
+		use crate::{LERead, LEWrite};
+		let mut writer = vec![];
+		writer.ewrite(BomAwareString("endio")).unwrap();
+		writer.ewrite(BomAwareString(String::from("\u{feff}endio"))).unwrap();
+		assert_eq!(writer, b"\x05\x00\x00\x00endio\x05\x00\x00\x00endio");
+		let mut reader = &writer[..];
+		let val: BomAwareString<String> = reader.eread().unwrap();
+		assert_eq!(val.0, "endio");
+		let val: BomAwareString<String> = reader.eread().unwrap();
+		assert_eq!(val.0, "endio");
+	}
+
+	#[test]
+	fn policy_string() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = INVALID;
+		let val: PolicyString<Lossy> = reader.eread().unwrap();
+		assert_eq!(val.0, "end\u{fffd}io");
+		let mut reader = INVALID;
+		let err = reader.eread::<PolicyString<Strict>>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn bom_lossy() {
+		use crate::BERead;
+		let mut reader = &b"\x00\x00\x00\x07\xef\xbb\xbfend\xff"[..];
+		let val: BomAwareString<PolicyString<Lossy>> = reader.eread().unwrap();
+		assert_eq!(val.0.0, "end\u{fffd}");
+	}
+}
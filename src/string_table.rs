@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::Endianness;
+use crate::deserialize::invalid_data;
+
+/**
+	Writes each distinct string only once, replacing repeats with a back-reference.
+
+	Every string is written as a `u32` index into the table. If the index is one past the last entry of the table, the string is new, and is written right after the index in the same length-prefixed form as `&str`. All further occurrences of the string only write its index.
+
+	The reader rebuilds the same table while reading, so the same `StringTable` type is used on both sides, with one table per stream.
+
+	## Examples
+
+	```
+	use endio::{LittleEndian, StringTable};
+
+	let mut table = StringTable::new();
+	let mut writer = vec![];
+	table.write::<LittleEndian, _>(&mut writer, "endio").unwrap();
+	table.write::<LittleEndian, _>(&mut writer, "endio").unwrap();
+	assert_eq!(writer, b"\x00\x00\x00\x00\x05\x00\x00\x00endio\x00\x00\x00\x00");
+
+	let mut table = StringTable::new();
+	let mut reader = &writer[..];
+	assert_eq!(table.read::<LittleEndian, _>(&mut reader).unwrap(), "endio");
+	assert_eq!(table.read::<LittleEndian, _>(&mut reader).unwrap(), "endio");
+	```
+*/
+#[derive(Debug, Default)]
+pub struct StringTable {
+	strings: Vec<String>,
+	indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The number of distinct strings in the table.
+	pub fn len(&self) -> usize {
+		self.strings.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.strings.is_empty()
+	}
+
+	/// Returns the string at the index, if there is one.
+	pub fn get(&self, index: usize) -> Option<&str> {
+		self.strings.get(index).map(String::as_str)
+	}
+
+	/// Writes the string's index, followed by the string itself if it hasn't been written before.
+	pub fn write<E: Endianness, W: Write>(&mut self, writer: &mut W, string: &str) -> Res<()> {
+		if let Some(&index) = self.indices.get(string) {
+			return E::serialize(index, writer);
+		}
+		let index = u32::try_from(self.strings.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "string table is full"))?;
+		E::serialize(index, writer)?;
+		E::serialize(string, writer)?;
+		self.strings.push(string.to_owned());
+		self.indices.insert(string.to_owned(), index);
+		Ok(())
+	}
+
+	/**
+		Reads a string's index, and the string itself if the index refers to a new entry. Returns an `InvalidData` error for indices past the end of the table.
+
+		Strings read are added to the table for writing as well, so the table can be used to write back to the same stream.
+	*/
+	pub fn read<E: Endianness, R: Read>(&mut self, reader: &mut R) -> Res<String> {
+		let index: u32 = E::deserialize(reader)?;
+		let index = index as usize;
+		if let Some(string) = self.strings.get(index) {
+			return Ok(string.clone());
+		}
+		if index != self.strings.len() {
+			return Err(invalid_data(format!("string table index {} out of range for {} entries", index, self.strings.len())));
+		}
+		let string: String = E::deserialize(reader)?;
+		self.strings.push(string.clone());
+		self.indices.entry(string.clone()).or_insert(index as u32);
+		Ok(string)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+	use std::io::{Read, Write};
+	use std::io::Result as Res;
+
+	use crate::{Endianness, LittleEndian, BigEndian};
+	use super::StringTable;
+
+	struct Names {
+		first: String,
+		second: String,
+		third: String,
+	}
+
+	impl Names {
+		fn write<E: Endianness, W: Write>(&self, table: &mut StringTable, writer: &mut W) -> Res<()> {
+			table.write::<E, W>(writer, &self.first)?;
+			table.write::<E, W>(writer, &self.second)?;
+			table.write::<E, W>(writer, &self.third)
+		}
+
+		fn read<E: Endianness, R: Read>(table: &mut StringTable, reader: &mut R) -> Res<Self> {
+			let first = table.read::<E, R>(reader)?;
+			let second = table.read::<E, R>(reader)?;
+			let third = table.read::<E, R>(reader)?;
+			Ok(Names { first, second, third })
+		}
+	}
+
+	#[test]
+	fn repeated_strings() {
+		let names = Names { first: "endio".into(), second: "endio".into(), third: "endio".into() };
+		let mut table = StringTable::new();
+		let mut writer = vec![];
+		names.write::<BigEndian, _>(&mut table, &mut writer).unwrap();
+		assert_eq!(table.len(), 1);
+		assert_eq!(writer, b"\x00\x00\x00\x00\x00\x00\x00\x05endio\x00\x00\x00\x00\x00\x00\x00\x00");
+
+		let mut table = StringTable::new();
+		let mut reader = &writer[..];
+		let read = Names::read::<BigEndian, _>(&mut table, &mut reader).unwrap();
+		assert_eq!(table.len(), 1);
+		assert_eq!(table.get(0), Some("endio"));
+		assert_eq!(read.first, "endio");
+		assert_eq!(read.second, "endio");
+		assert_eq!(read.third, "endio");
+	}
+
+	#[test]
+	fn distinct_strings() {
+		let mut table = StringTable::new();
+		let mut writer = vec![];
+		table.write::<LittleEndian, _>(&mut writer, "a").unwrap();
+		table.write::<LittleEndian, _>(&mut writer, "b").unwrap();
+		table.write::<LittleEndian, _>(&mut writer, "a").unwrap();
+		assert_eq!(table.len(), 2);
+		assert_eq!(writer, b"\x00\x00\x00\x00\x01\x00\x00\x00a\x01\x00\x00\x00\x01\x00\x00\x00b\x00\x00\x00\x00");
+	}
+
+	#[test]
+	fn index_out_of_range() {
+		let mut table = StringTable::new();
+		let mut reader = &b"\x01\x00\x00\x00"[..];
+		let err = table.read::<LittleEndian, _>(&mut reader).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn write_after_read() {
+		let mut table = StringTable::new();
+		let mut reader = &b"\x00\x00\x00\x00\x05\x00\x00\x00endio"[..];
+		assert_eq!(table.read::<LittleEndian, _>(&mut reader).unwrap(), "endio");
+		let mut writer = vec![];
+		table.write::<LittleEndian, _>(&mut writer, "endio").unwrap();
+		assert_eq!(table.len(), 1);
+		assert_eq!(writer, b"\x00\x00\x00\x00");
+	}
+}
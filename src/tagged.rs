@@ -0,0 +1,215 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Endianness, LittleEndian};
+use crate::config::check_len;
+use crate::deserialize::{invalid_data, read_bytes};
+use crate::varint::{read_varint, write_varint};
+
+const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/**
+	Writes a message of protobuf-style tagged fields.
+
+	Each field is written as a varint tag of `field_number << 3 | wire_type`, followed by the value in the format of its wire type:
+
+	- 0, `varint`: An unsigned LEB128 varint.
+	- 1, `fixed64`: A `u64` in little endian.
+	- 2, `bytes`: A varint length, followed by that many bytes. Also used for strings and nested messages.
+	- 5, `fixed32`: A `u32` in little endian.
+
+	Fixed-size values are always little endian, as in protobuf, regardless of the writer. Field numbers must be in `1..=2^29-1`, otherwise an `InvalidInput` error is returned. Read the message back with `TaggedFieldReader`.
+
+	## Examples
+
+	```
+	use endio::TaggedFields;
+
+	let mut fields = TaggedFields::new(vec![]);
+	fields.varint(1, 150).unwrap().bytes(2, b"endio").unwrap();
+	assert_eq!(fields.into_inner(), b"\x08\x96\x01\x12\x05endio");
+	```
+*/
+#[derive(Debug)]
+pub struct TaggedFields<W> {
+	writer: W,
+}
+
+impl<W: Write> TaggedFields<W> {
+	pub fn new(writer: W) -> Self {
+		Self { writer }
+	}
+
+	pub fn into_inner(self) -> W {
+		self.writer
+	}
+
+	fn tag(&mut self, number: u32, wire_type: u8) -> Res<()> {
+		if number == 0 || number > MAX_FIELD_NUMBER {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("field number {} out of range 1..={}", number, MAX_FIELD_NUMBER)));
+		}
+		write_varint(&mut self.writer, (number as u64) << 3 | wire_type as u64)
+	}
+
+	/// Writes a field of wire type 0, the value as a varint.
+	pub fn varint(&mut self, number: u32, value: u64) -> Res<&mut Self> {
+		self.tag(number, 0)?;
+		write_varint(&mut self.writer, value)?;
+		Ok(self)
+	}
+
+	/// Writes a field of wire type 1, the value in little endian.
+	pub fn fixed64(&mut self, number: u32, value: u64) -> Res<&mut Self> {
+		self.tag(number, 1)?;
+		LittleEndian::serialize(value, &mut self.writer)?;
+		Ok(self)
+	}
+
+	/// Writes a field of wire type 2, a varint length followed by the bytes.
+	pub fn bytes(&mut self, number: u32, value: &[u8]) -> Res<&mut Self> {
+		self.tag(number, 2)?;
+		write_varint(&mut self.writer, value.len() as u64)?;
+		self.writer.write_all(value)?;
+		Ok(self)
+	}
+
+	/// Writes a field of wire type 5, the value in little endian.
+	pub fn fixed32(&mut self, number: u32, value: u32) -> Res<&mut Self> {
+		self.tag(number, 5)?;
+		LittleEndian::serialize(value, &mut self.writer)?;
+		Ok(self)
+	}
+}
+
+/// The value of a field read by `TaggedFieldReader`, by wire type. See `TaggedFields` for the formats.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue {
+	Varint(u64),
+	Fixed64(u64),
+	Bytes(Vec<u8>),
+	Fixed32(u32),
+}
+
+/**
+	Reads the fields of a message written by `TaggedFields`, as an iterator of field numbers and values.
+
+	The message extends to the end of the reader, so limit the reader with `take` to read an embedded message. The iterator ends when the reader ends at a field boundary. Reaching the end within a field is an `UnexpectedEof` error, and field number 0 or a wire type other than 0, 1, 2 and 5 is an `InvalidData` error. The groups of wire types 3 and 4 are deprecated in protobuf and not supported.
+
+	## Examples
+
+	```
+	use endio::{FieldValue, TaggedFieldReader};
+
+	let mut fields = TaggedFieldReader::new(&b"\x08\x96\x01\x12\x05endio"[..]);
+	assert_eq!(fields.next().unwrap().unwrap(), (1, FieldValue::Varint(150)));
+	assert_eq!(fields.next().unwrap().unwrap(), (2, FieldValue::Bytes(b"endio".to_vec())));
+	assert!(fields.next().is_none());
+	```
+*/
+#[derive(Debug)]
+pub struct TaggedFieldReader<R> {
+	reader: R,
+}
+
+impl<R: Read> TaggedFieldReader<R> {
+	pub fn new(reader: R) -> Self {
+		Self { reader }
+	}
+
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	/// Reads the next field, or `None` if the reader is at its end.
+	pub fn read_field(&mut self) -> Res<Option<(u32, FieldValue)>> {
+		let mut first = [0];
+		loop {
+			match self.reader.read(&mut first) {
+				Ok(0) => return Ok(None),
+				Ok(_) => break,
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e),
+			}
+		}
+		let tag = read_varint(&mut (&first[..]).chain(&mut self.reader))?;
+		let number = tag >> 3;
+		if number == 0 || number > MAX_FIELD_NUMBER as u64 {
+			return Err(invalid_data(format!("field number {} out of range 1..={}", number, MAX_FIELD_NUMBER)));
+		}
+		let value = match tag & 0x7 {
+			0 => FieldValue::Varint(read_varint(&mut self.reader)?),
+			1 => FieldValue::Fixed64(LittleEndian::deserialize(&mut self.reader)?),
+			2 => {
+				let len = read_varint(&mut self.reader)?;
+				let len = usize::try_from(len).map_err(|_| invalid_data(format!("length {} does not fit in usize", len)))?;
+				FieldValue::Bytes(read_bytes(&mut self.reader, check_len(len)?)?)
+			}
+			5 => FieldValue::Fixed32(LittleEndian::deserialize(&mut self.reader)?),
+			wire_type => return Err(invalid_data(format!("field {} had unsupported wire type {}", number, wire_type))),
+		};
+		Ok(Some((number as u32, value)))
+	}
+}
+
+impl<R: Read> Iterator for TaggedFieldReader<R> {
+	type Item = Res<(u32, FieldValue)>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.read_field().transpose()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{FieldValue, TaggedFieldReader, TaggedFields};
+
+	#[test]
+	fn two_fields() {
+		let mut fields = TaggedFields::new(vec![]);
+		fields.varint(1, 300).unwrap().bytes(15, b"io").unwrap();
+		let bytes = fields.into_inner();
+		assert_eq!(bytes, b"\x08\xac\x02\x7a\x02io");
+		let read: Vec<_> = TaggedFieldReader::new(&bytes[..]).collect::<Result<_, _>>().unwrap();
+		assert_eq!(read, [(1, FieldValue::Varint(300)), (15, FieldValue::Bytes(b"io".to_vec()))]);
+	}
+
+	#[test]
+	fn fixed() {
+		let mut fields = TaggedFields::new(vec![]);
+		fields.fixed32(16, 0xbaadf00d).unwrap().fixed64(2, 42).unwrap();
+		let bytes = fields.into_inner();
+		assert_eq!(bytes, b"\x85\x01\x0d\xf0\xad\xba\x11\x2a\x00\x00\x00\x00\x00\x00\x00");
+		let mut reader = TaggedFieldReader::new(&bytes[..]);
+		assert_eq!(reader.read_field().unwrap(), Some((16, FieldValue::Fixed32(0xbaadf00d))));
+		assert_eq!(reader.read_field().unwrap(), Some((2, FieldValue::Fixed64(42))));
+		assert_eq!(reader.read_field().unwrap(), None);
+	}
+
+	#[test]
+	fn invalid_field_number() {
+		use std::io;
+		let mut fields = TaggedFields::new(vec![]);
+		let err = fields.varint(0, 1).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(fields.into_inner().is_empty());
+		let err = TaggedFieldReader::new(&b"\x00\x01"[..]).read_field().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+
+	#[test]
+	fn unsupported_wire_type() {
+		use std::io;
+		let err = TaggedFieldReader::new(&b"\x0b"[..]).read_field().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "field 1 had unsupported wire type 3");
+	}
+
+	#[test]
+	fn truncated() {
+		use std::io;
+		let err = TaggedFieldReader::new(&b"\x12\x05end"[..]).read_field().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
@@ -0,0 +1,96 @@
+use std::io::Result as Res;
+use std::marker::PhantomData;
+
+use crate::{BigEndian, Deserialize, Endianness, LittleEndian, Serialize};
+
+/**
+	A reader or writer with its endianness fixed, so that neither the `*Read`/`*Write` traits nor the endianness need to be named.
+
+	## Examples
+
+	```
+	use endio::Endio;
+
+	let mut writer = Endio::little_endian(vec![]);
+	writer.write(0xbaadu16).unwrap();
+	writer.write("endio").unwrap();
+	let bytes = writer.into_inner();
+	assert_eq!(bytes, b"\xad\xba\x05\x00\x00\x00endio");
+
+	let mut reader = Endio::little_endian(&bytes[..]);
+	assert_eq!(reader.read::<u16>().unwrap(), 0xbaad);
+	assert_eq!(reader.read::<String>().unwrap(), "endio");
+	```
+*/
+#[derive(Debug)]
+pub struct Endio<T, E> {
+	inner: T,
+	endianness: PhantomData<E>,
+}
+
+impl<T> Endio<T, BigEndian> {
+	/// Wraps the reader or writer to read and write in big endian.
+	pub fn big_endian(inner: T) -> Self {
+		Self { inner, endianness: PhantomData }
+	}
+}
+
+impl<T> Endio<T, LittleEndian> {
+	/// Wraps the reader or writer to read and write in little endian.
+	pub fn little_endian(inner: T) -> Self {
+		Self { inner, endianness: PhantomData }
+	}
+}
+
+impl<T, E: Endianness> Endio<T, E> {
+	/// Writes the value in this wrapper's endianness.
+	pub fn write<S: Serialize<E, T>>(&mut self, value: S) -> Res<()> {
+		E::serialize(value, &mut self.inner)
+	}
+
+	/// Reads a value in this wrapper's endianness.
+	pub fn read<D: Deserialize<E, T>>(&mut self) -> Res<D> {
+		E::deserialize(&mut self.inner)
+	}
+
+	pub fn get_ref(&self) -> &T {
+		&self.inner
+	}
+
+	pub fn get_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Endio;
+
+	#[test]
+	fn big_endian() {
+		let mut writer = Endio::big_endian(vec![]);
+		writer.write(0xbaadu16).unwrap();
+		writer.write((true, 42u8)).unwrap();
+		assert_eq!(writer.get_ref(), b"\xba\xad\x01\x2a");
+		let bytes = writer.into_inner();
+		let mut reader = Endio::big_endian(&bytes[..]);
+		assert_eq!(reader.read::<u16>().unwrap(), 0xbaad);
+		assert_eq!(reader.read::<(bool, u8)>().unwrap(), (true, 42));
+		assert!(reader.get_ref().is_empty());
+	}
+
+	#[test]
+	fn little_endian() {
+		let mut writer = Endio::little_endian(vec![]);
+		writer.write(0xdeadbeefu32).unwrap();
+		assert_eq!(writer.get_ref(), b"\xef\xbe\xad\xde");
+		let bytes = writer.into_inner();
+		let mut reader = Endio::little_endian(&bytes[..]);
+		assert_eq!(reader.read::<u32>().unwrap(), 0xdeadbeef);
+		assert!(reader.read::<u8>().is_err());
+	}
+}
@@ -0,0 +1,200 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::{Deserialize, Endianness, Serialize};
+use crate::deserialize::{invalid_data, read_bytes, read_len};
+use crate::serialize::write_len;
+
+/// How deeply lists and maps can be nested when reading a `Value`, so that malicious input can't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/**
+	A dynamically typed value, written with a `u8` type tag so that it can be read back without knowing its type.
+
+	The tag is followed by the value in its usual format:
+
+	| Variant  | Tag | Contents |
+	|----------|-----|----------|
+	| `Bool`   | 0   | `bool` |
+	| `I64`    | 1   | `i64` |
+	| `U64`    | 2   | `u64` |
+	| `F64`    | 3   | `f64` |
+	| `String` | 4   | `u32` length prefix and UTF-8 bytes |
+	| `Bytes`  | 5   | `u32` length prefix and bytes |
+	| `List`   | 6   | `u32` length prefix and that many values |
+	| `Map`    | 7   | `u32` length prefix and that many key and value pairs |
+
+	Reading returns an `InvalidData` error for an unknown tag, or if lists and maps are nested more than 128 levels deep.
+
+	## Examples
+
+	```
+	use endio::{LERead, LEWrite, Value};
+
+	let value = Value::List(vec![Value::Bool(true), Value::String("endio".into())]);
+	let mut writer = vec![];
+	writer.ewrite(&value).unwrap();
+	assert_eq!(writer, b"\x06\x02\x00\x00\x00\x00\x01\x04\x05\x00\x00\x00endio");
+
+	let mut reader = &writer[..];
+	let read: Value = reader.eread().unwrap();
+	assert_eq!(read, value);
+	```
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+	Bool(bool),
+	I64(i64),
+	U64(u64),
+	F64(f64),
+	String(String),
+	Bytes(Vec<u8>),
+	List(Vec<Value>),
+	/// Key and value pairs in order. Keys aren't required to be unique.
+	Map(Vec<(Value, Value)>),
+}
+
+impl<E: Endianness, W: Write> Serialize<E, W> for &Value {
+	fn serialize(self, writer: &mut W) -> Res<()> {
+		match self {
+			Value::Bool(val) => {
+				E::serialize(0u8, writer)?;
+				E::serialize(*val, writer)
+			}
+			Value::I64(val) => {
+				E::serialize(1u8, writer)?;
+				E::serialize(*val, writer)
+			}
+			Value::U64(val) => {
+				E::serialize(2u8, writer)?;
+				E::serialize(*val, writer)
+			}
+			Value::F64(val) => {
+				E::serialize(3u8, writer)?;
+				E::serialize(*val, writer)
+			}
+			Value::String(val) => {
+				E::serialize(4u8, writer)?;
+				E::serialize(val, writer)
+			}
+			Value::Bytes(val) => {
+				E::serialize(5u8, writer)?;
+				write_len::<E, W>(writer, val.len())?;
+				writer.write_all(val)
+			}
+			Value::List(vals) => {
+				E::serialize(6u8, writer)?;
+				write_len::<E, W>(writer, vals.len())?;
+				for val in vals {
+					E::serialize(val, writer)?;
+				}
+				Ok(())
+			}
+			Value::Map(entries) => {
+				E::serialize(7u8, writer)?;
+				write_len::<E, W>(writer, entries.len())?;
+				for (key, val) in entries {
+					E::serialize(key, writer)?;
+					E::serialize(val, writer)?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+fn read_value<E: Endianness, R: Read>(reader: &mut R, depth: usize) -> Res<Value> {
+	let tag: u8 = E::deserialize(reader)?;
+	Ok(match tag {
+		0 => Value::Bool(E::deserialize(reader)?),
+		1 => Value::I64(E::deserialize(reader)?),
+		2 => Value::U64(E::deserialize(reader)?),
+		3 => Value::F64(E::deserialize(reader)?),
+		4 => Value::String(E::deserialize(reader)?),
+		5 => {
+			let len = read_len::<E, R>(reader)?;
+			Value::Bytes(read_bytes(reader, len)?)
+		}
+		6 | 7 if depth == MAX_DEPTH => return Err(invalid_data(format!("Value is nested more than {} levels deep", MAX_DEPTH))),
+		6 => {
+			let len = read_len::<E, R>(reader)?;
+			let mut vals = vec![];
+			for _ in 0..len {
+				vals.push(read_value::<E, R>(reader, depth + 1)?);
+			}
+			Value::List(vals)
+		}
+		7 => {
+			let len = read_len::<E, R>(reader)?;
+			let mut entries = vec![];
+			for _ in 0..len {
+				let key = read_value::<E, R>(reader, depth + 1)?;
+				let val = read_value::<E, R>(reader, depth + 1)?;
+				entries.push((key, val));
+			}
+			Value::Map(entries)
+		}
+		_ => return Err(invalid_data(format!("Value had unknown tag {}", tag))),
+	})
+}
+
+impl<E: Endianness, R: Read> Deserialize<E, R> for Value {
+	fn deserialize(reader: &mut R) -> Res<Self> {
+		read_value::<E, R>(reader, 0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Value;
+
+	fn round_trip(value: Value, expected: &[u8]) {
+		use crate::{BERead, BEWrite};
+		let mut writer = vec![];
+		writer.ewrite(&value).unwrap();
+		assert_eq!(writer, expected);
+		let mut reader = &writer[..];
+		let val: Value = reader.eread().unwrap();
+		assert_eq!(val, value);
+		assert!(reader.is_empty());
+	}
+
+	#[test]
+	fn primitives() {
+		round_trip(Value::Bool(true), b"\x00\x01");
+		round_trip(Value::I64(-2), b"\x01\xff\xff\xff\xff\xff\xff\xff\xfe");
+		round_trip(Value::U64(0xbaad), b"\x02\x00\x00\x00\x00\x00\x00\xba\xad");
+		round_trip(Value::F64(0.5), b"\x03\x3f\xe0\x00\x00\x00\x00\x00\x00");
+		round_trip(Value::String("endio".into()), b"\x04\x00\x00\x00\x05endio");
+		round_trip(Value::Bytes(vec![0xba, 0xad]), b"\x05\x00\x00\x00\x02\xba\xad");
+	}
+
+	#[test]
+	fn nested() {
+		let value = Value::Map(vec![
+			(Value::String("list".into()), Value::List(vec![Value::U64(1), Value::List(vec![])])),
+		]);
+		round_trip(value, b"\x07\x00\x00\x00\x01\x04\x00\x00\x00\x04list\x06\x00\x00\x00\x02\x02\x00\x00\x00\x00\x00\x00\x00\x01\x06\x00\x00\x00\x00");
+	}
+
+	#[test]
+	fn unknown_tag() {
+		use std::io;
+		use crate::LERead;
+		let mut reader = &b"\x08"[..];
+		let err = reader.eread::<Value>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "Value had unknown tag 8");
+	}
+
+	#[test]
+	fn too_deep() {
+		use std::io;
+		use crate::LERead;
+		let data: Vec<u8> = std::iter::repeat_n(&b"\x06\x01\x00\x00\x00"[..], 200).flatten().copied().collect();
+		let mut reader = &data[..];
+		let err = reader.eread::<Value>().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(err.to_string(), "Value is nested more than 128 levels deep");
+	}
+}
@@ -0,0 +1,40 @@
+use std::io::{Read, Write};
+use std::io::Result as Res;
+
+use crate::deserialize::invalid_data;
+
+/// Writes an unsigned LEB128 varint: 7 bits per byte, least significant group first, with the high bit set on all but the last byte.
+pub(crate) fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> Res<()> {
+	let mut buf = [0; 10];
+	let mut len = 0;
+	loop {
+		let byte = (value & 0x7f) as u8;
+		value >>= 7;
+		if value == 0 {
+			buf[len] = byte;
+			len += 1;
+			break;
+		}
+		buf[len] = byte | 0x80;
+		len += 1;
+	}
+	writer.write_all(&buf[..len])
+}
+
+/// Reads an unsigned LEB128 varint, returning an `InvalidData` error if it overflows a `u64`.
+pub(crate) fn read_varint<R: Read>(reader: &mut R) -> Res<u64> {
+	let mut value = 0u64;
+	for shift in (0..64).step_by(7) {
+		let mut byte = [0];
+		reader.read_exact(&mut byte)?;
+		let bits = (byte[0] & 0x7f) as u64;
+		if bits << shift >> shift != bits {
+			break;
+		}
+		value |= bits << shift;
+		if byte[0] & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	Err(invalid_data("varint overflows u64".into()))
+}
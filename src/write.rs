@@ -1,13 +1,40 @@
 use std::io::Write;
 use std::io::Result as Res;
 
-use crate::{BigEndian, Endianness, LittleEndian, Serialize};
+use crate::{BigEndian, CountingWriter, Endianness, LittleEndian, Serialize};
+use crate::serialize::write_len;
+
+fn write_checked<E: Endianness, W: Write, S: Serialize<E, CountingWriter<W>>>(writer: W, ser: S) -> Res<()> {
+	let mut counter = CountingWriter::new(writer);
+	E::serialize(ser, &mut counter)?;
+	if let Some(size) = S::SIZE {
+		debug_assert_eq!(counter.count(), size as u64, "value was written as a different number of bytes than its Serialize::SIZE");
+	}
+	Ok(())
+}
+
+fn write_header<E: Endianness, W, F: FnOnce(&mut W) -> Res<()>>(writer: &mut W, magic: &[u8], version: u16, body: F) -> Res<()> where u8: Serialize<E, W>, u16: Serialize<E, W> {
+	for &byte in magic {
+		E::serialize(byte, writer)?;
+	}
+	E::serialize(version, writer)?;
+	body(writer)
+}
+
+fn write_length_prefixed_buffered<E: Endianness, W: Write, F: FnOnce(&mut Vec<u8>) -> Res<()>>(writer: &mut W, body: F) -> Res<()> {
+	let mut buf = vec![];
+	body(&mut buf)?;
+	write_len::<E, W>(writer, buf.len())?;
+	writer.write_all(&buf)
+}
 
 /**
 	Only necessary for custom (de-)serializations.
 
 	Interface for writing data with a specified endianness. Use this interface to make serializations automatically switch endianness without having to write the same code twice.
 
+	Trait objects can't be used as `Self` directly, since `dyn Write` isn't `Sized`. Use `&mut dyn Write` or `Box<dyn Write>` instead, which are writers themselves and so implement this trait.
+
 	In theory this would be the only write trait and BE-/LEWrite would be aliases to the BE/LE type parameter variants, but for some reason that doesn't import methods in `use` notation.
 
 	## Examples
@@ -34,6 +61,69 @@ pub trait EWrite<E: Endianness>: Sized {
 	fn write_be<S: Serialize<BigEndian,    Self>>(&mut self, ser: S) -> Res<()> { BigEndian::serialize(ser, self) }
 	/// Writes in forced little endian.
 	fn write_le<S: Serialize<LittleEndian, Self>>(&mut self, ser: S) -> Res<()> { LittleEndian::serialize(ser, self) }
+	/**
+		Writes a file header consisting of the magic bytes as-is and a `u16` version in the writer's endianness, followed by the body written by the closure.
+
+		Read the header back with `read_header`.
+
+		## Examples
+
+		```
+		use endio::LEWrite;
+
+		let mut writer = vec![];
+		writer.with_header(b"ENDI", 2, |w| w.ewrite(42u8)).unwrap();
+		assert_eq!(writer, b"ENDI\x02\x00\x2a");
+		```
+	*/
+	fn with_header<F: FnOnce(&mut Self) -> Res<()>>(&mut self, magic: &[u8], version: u16, body: F) -> Res<()> where u8: Serialize<E, Self>, u16: Serialize<E, Self> { write_header::<E, _, _>(self, magic, version, body) }
+	/**
+		Writes like `ewrite`, but returns the writer to allow chaining writes.
+
+		## Examples
+
+		```
+		use endio::LEWrite;
+
+		let mut writer = vec![];
+		writer.write_chained(42u8)?.write_chained(true)?.write_chained(754187983u32)?;
+		assert_eq!(writer, b"\x2a\x01\xcf\xfe\xf3\x2c");
+		# Ok::<(), std::io::Error>(())
+		```
+	*/
+	fn write_chained<S: Serialize<E, Self>>(&mut self, ser: S) -> Res<&mut Self> { E::serialize(ser, self)?; Ok(self) }
+	/**
+		Writes like `ewrite`, but in debug builds asserts that exactly `Serialize::SIZE` bytes were written, if the type declares a size.
+
+		This catches fixed-size impls whose output doesn't match their declared size.
+
+		## Examples
+
+		```
+		use endio::LEWrite;
+
+		let mut writer = vec![];
+		writer.write_checked((42u8, 0xbaadu16)).unwrap();
+		assert_eq!(writer, b"\x2a\xad\xba");
+		```
+	*/
+	fn write_checked<'a, S: Serialize<E, CountingWriter<&'a mut Self>>>(&'a mut self, ser: S) -> Res<()> where Self: Write { write_checked::<E, _, _>(self, ser) }
+	/**
+		Writes the body produced by the closure with a `u32` length prefix of its size in bytes.
+
+		The body is written into a buffer first, so that its length is known before anything is written. This works for any writer, including ones that can't seek back to fill in the length afterwards, at the cost of holding the whole body in memory.
+
+		## Examples
+
+		```
+		use endio::LEWrite;
+
+		let mut writer = vec![];
+		writer.write_length_prefixed_buffered(|w| w.ewrite(0xbaadu16)).unwrap();
+		assert_eq!(writer, b"\x02\x00\x00\x00\xad\xba");
+		```
+	*/
+	fn write_length_prefixed_buffered<F: FnOnce(&mut Vec<u8>) -> Res<()>>(&mut self, body: F) -> Res<()> where Self: Write { write_length_prefixed_buffered::<E, _, _>(self, body) }
 }
 
 /**
@@ -47,6 +137,10 @@ pub trait BEWrite: Sized {
 	fn ewrite   <S: Serialize<BigEndian,    Self>>(&mut self, ser: S) -> Res<()> { BigEndian::serialize(ser, self) }
 	fn write_be<S: Serialize<BigEndian,    Self>>(&mut self, ser: S) -> Res<()> { BigEndian::serialize(ser, self) }
 	fn write_le<S: Serialize<LittleEndian, Self>>(&mut self, ser: S) -> Res<()> { LittleEndian::serialize(ser, self) }
+	fn with_header<F: FnOnce(&mut Self) -> Res<()>>(&mut self, magic: &[u8], version: u16, body: F) -> Res<()> where u8: Serialize<BigEndian, Self>, u16: Serialize<BigEndian, Self> { write_header::<BigEndian, _, _>(self, magic, version, body) }
+	fn write_chained<S: Serialize<BigEndian, Self>>(&mut self, ser: S) -> Res<&mut Self> { BigEndian::serialize(ser, self)?; Ok(self) }
+	fn write_checked<'a, S: Serialize<BigEndian, CountingWriter<&'a mut Self>>>(&'a mut self, ser: S) -> Res<()> where Self: Write { write_checked::<BigEndian, _, _>(self, ser) }
+	fn write_length_prefixed_buffered<F: FnOnce(&mut Vec<u8>) -> Res<()>>(&mut self, body: F) -> Res<()> where Self: Write { write_length_prefixed_buffered::<BigEndian, _, _>(self, body) }
 }
 
 /**
@@ -60,6 +154,10 @@ pub trait LEWrite: Sized {
 	fn ewrite   <S: Serialize<LittleEndian, Self>>(&mut self, ser: S) -> Res<()> { LittleEndian::serialize(ser, self) }
 	fn write_be<S: Serialize<BigEndian,    Self>>(&mut self, ser: S) -> Res<()> { BigEndian::serialize(ser, self) }
 	fn write_le<S: Serialize<LittleEndian, Self>>(&mut self, ser: S) -> Res<()> { LittleEndian::serialize(ser, self) }
+	fn with_header<F: FnOnce(&mut Self) -> Res<()>>(&mut self, magic: &[u8], version: u16, body: F) -> Res<()> where u8: Serialize<LittleEndian, Self>, u16: Serialize<LittleEndian, Self> { write_header::<LittleEndian, _, _>(self, magic, version, body) }
+	fn write_chained<S: Serialize<LittleEndian, Self>>(&mut self, ser: S) -> Res<&mut Self> { LittleEndian::serialize(ser, self)?; Ok(self) }
+	fn write_checked<'a, S: Serialize<LittleEndian, CountingWriter<&'a mut Self>>>(&'a mut self, ser: S) -> Res<()> where Self: Write { write_checked::<LittleEndian, _, _>(self, ser) }
+	fn write_length_prefixed_buffered<F: FnOnce(&mut Vec<u8>) -> Res<()>>(&mut self, body: F) -> Res<()> where Self: Write { write_length_prefixed_buffered::<LittleEndian, _, _>(self, body) }
 }
 
 impl<W: Write, E: Endianness> EWrite<E> for W {}
@@ -85,4 +183,95 @@ mod tests {
 		writer.write_le(0xadbau16).unwrap();
 		assert_eq!(&writer[..], DATA);
 	}
+
+	#[test]
+	fn write_dyn() {
+		use std::io::Write;
+		use crate::BEWrite;
+		let mut vec = vec![];
+		{
+			let mut writer: &mut dyn Write = &mut vec;
+			writer.ewrite(0xbaadu16).unwrap();
+			writer.ewrite("io").unwrap();
+		}
+		assert_eq!(vec, b"\xba\xad\x00\x00\x00\x02io");
+		let mut writer: Box<dyn Write> = Box::new(vec![]);
+		writer.ewrite(0xbaadu16).unwrap();
+	}
+
+	#[test]
+	fn write_header() {
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.with_header(b"ENDI", 0xbaad, |w| w.ewrite(0xf00du16)).unwrap();
+		assert_eq!(writer, b"ENDI\xba\xad\xf0\x0d");
+	}
+
+	#[test]
+	fn write_length_prefixed_buffered() {
+		use std::io::{Result, Write};
+		use crate::BEWrite;
+
+		/// Only implements `Write`, like a socket.
+		struct Unseekable(Vec<u8>);
+
+		impl Write for Unseekable {
+			fn write(&mut self, buf: &[u8]) -> Result<usize> {
+				self.0.write(buf)
+			}
+
+			fn flush(&mut self) -> Result<()> {
+				Ok(())
+			}
+		}
+
+		let mut writer = Unseekable(vec![]);
+		writer.write_length_prefixed_buffered(|w| {
+			w.ewrite(0xbaadu16)?;
+			w.ewrite("io")
+		}).unwrap();
+		writer.ewrite(42u8).unwrap();
+		assert_eq!(writer.0, b"\x00\x00\x00\x08\xba\xad\x00\x00\x00\x02io\x2a");
+	}
+
+	#[test]
+	fn write_chained() {
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.write_chained(0xbaadu16).unwrap().write_chained(0xf00du16).unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d");
+	}
+
+	#[test]
+	fn write_checked() {
+		use crate::BEWrite;
+		let mut writer = vec![];
+		writer.write_checked([0xbaadu16, 0xf00d]).unwrap();
+		writer.write_checked("endio").unwrap();
+		assert_eq!(writer, b"\xba\xad\xf0\x0d\x00\x00\x00\x05endio");
+		use crate::{BigEndian, Serialize};
+		assert_eq!(<(u8, [u16; 2], &char) as Serialize<BigEndian, Vec<u8>>>::SIZE, Some(9));
+		assert_eq!(<(u8, &str) as Serialize<BigEndian, Vec<u8>>>::SIZE, None);
+	}
+
+	#[cfg(debug_assertions)]
+	#[test]
+	#[should_panic(expected = "different number of bytes than its Serialize::SIZE")]
+	fn write_checked_mismatch() {
+		use std::io::{Result, Write};
+		use crate::{Endianness, LEWrite, Serialize};
+
+		struct Truncated(u32);
+
+		impl<E: Endianness, W: Write> Serialize<E, W> for Truncated {
+			const SIZE: Option<usize> = Some(4);
+
+			fn serialize(self, writer: &mut W) -> Result<()> {
+				writer.write_all(&self.0.to_le_bytes()[..2])
+			}
+		}
+
+		let mut writer = vec![];
+		writer.write_checked(Truncated(0xbaadf00d)).unwrap();
+	}
 }